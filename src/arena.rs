@@ -0,0 +1,142 @@
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::CapacityError;
+
+/// A bump allocator over an inline buffer of CAP bytes.
+///
+/// Values are allocated by bumping an offset into the buffer, and
+/// are all freed at once with [reset](Self::reset), which is O(1).
+///
+/// Note that the arena never runs the destructors of the
+/// values allocated on it.
+///
+/// Each allocation returns a mutable reference to a region
+/// that doesn't overlap with any other, so allocating through
+/// a shared reference is sound.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackArena;
+///
+/// let mut arena = StackArena::<64>::new();
+///
+/// let n = arena.alloc(12_u32).unwrap();
+/// let s = arena.alloc_slice(&[1_u16, 2, 3]).unwrap();
+/// *n += 1;
+/// s[0] = 0;
+///
+/// assert_eq!(*n, 13);
+/// assert_eq!(s, &[0, 2, 3]);
+///
+/// arena.reset();
+/// assert_eq!(arena.used(), 0);
+/// ```
+pub struct StackArena<const CAP: usize> {
+    buf: UnsafeCell<[MaybeUninit<u8>; CAP]>,
+    offset: Cell<usize>,
+}
+
+impl<const CAP: usize> StackArena<CAP> {
+    /// Creates a new empty StackArena
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: UnsafeCell::new([MaybeUninit::uninit(); CAP]),
+            offset: Cell::new(0),
+        }
+    }
+
+    /// Reserves a properly aligned region of memory for the given layout
+    fn alloc_layout(&self, layout: Layout) -> Result<*mut u8, CapacityError> {
+        let base = self.buf.get() as *mut u8;
+        let offset = self.offset.get();
+
+        let addr = (base as usize).checked_add(offset).ok_or(CapacityError)?;
+        let padding = addr.next_multiple_of(layout.align()) - addr;
+
+        let start = offset.checked_add(padding).ok_or(CapacityError)?;
+        let end = start.checked_add(layout.size()).ok_or(CapacityError)?;
+        if end > CAP {
+            return Err(CapacityError);
+        }
+        self.offset.set(end);
+
+        /* SAFETY: start <= end <= CAP, so the pointer is within the buffer */
+        Ok(unsafe { base.add(start) })
+    }
+
+    /// Allocates the given value on the arena, returning
+    /// a mutable reference to it.
+    ///
+    /// # Errors
+    /// If there's not enough space left, returns back the
+    /// value inside an Err variant.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T>(&self, value: T) -> Result<&mut T, T> {
+        match self.alloc_layout(Layout::new::<T>()) {
+            Ok(ptr) => {
+                let ptr = ptr as *mut T;
+                unsafe {
+                    /* SAFETY: The pointer is aligned and points to enough
+                     * space for a T, that no other reference overlaps */
+                    ptr.write(value);
+                    Ok(&mut *ptr)
+                }
+            }
+            Err(_) => Err(value),
+        }
+    }
+
+    /// Copies the given slice into the arena, returning
+    /// a mutable reference to the copy.
+    ///
+    /// # Errors
+    /// If there's not enough space left
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice<T: Copy>(&self, src: &[T]) -> Result<&mut [T], CapacityError> {
+        let layout = Layout::array::<T>(src.len()).map_err(|_| CapacityError)?;
+        let ptr = self.alloc_layout(layout)? as *mut T;
+        unsafe {
+            /* SAFETY: The region is aligned and big enough for the slice */
+            ptr::copy_nonoverlapping(src.as_ptr(), ptr, src.len());
+            Ok(core::slice::from_raw_parts_mut(ptr, src.len()))
+        }
+    }
+
+    /// Frees all the values allocated on this arena.
+    ///
+    /// Since this takes a mutable reference, the borrow checker
+    /// guarantees that no allocated value is still in use.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.offset.set(0);
+    }
+
+    /// Returns the number of bytes used, including alignment padding
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Returns the number of bytes left in the arena
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        CAP - self.offset.get()
+    }
+
+    /// Returns the capacity of this StackArena, in bytes
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+impl<const CAP: usize> Default for StackArena<CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,158 @@
+use core::cell::UnsafeCell;
+use core::mem::{self, MaybeUninit};
+use core::slice;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// An append-only vector that can be pushed to through a shared reference.
+///
+/// Pushing claims a slot with an atomic operation, so several
+/// execution contexts (threads, interrupt handlers...) can push
+/// concurrently without locking. Elements can't be removed or
+/// modified, which allows readers to take a snapshot of the
+/// elements pushed so far with [as_slice](Self::as_slice).
+///
+/// # Example
+/// ```
+/// use stack_vector::AtomicStackVec;
+///
+/// let log = AtomicStackVec::<u32, 64>::new();
+///
+/// std::thread::scope(|s| {
+///     for t in 0..4 {
+///         let log = &log;
+///         s.spawn(move || {
+///             for i in 0..10 {
+///                 log.push(t * 100 + i).unwrap();
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(log.len(), 40);
+/// assert!(log.as_slice().contains(&309));
+/// ```
+pub struct AtomicStackVec<T, const CAP: usize> {
+    inner: [UnsafeCell<MaybeUninit<T>>; CAP],
+    ready: [AtomicBool; CAP],
+    /* Number of slots claimed by pushers */
+    claimed: AtomicUsize,
+    /* Number of leading slots that are initialized,
+     * and thus visible to readers */
+    committed: AtomicUsize,
+}
+
+/* SAFETY: Each slot is written by exactly one pusher before being
+ * published, and after that, it's only accessed through shared references */
+unsafe impl<T: Send + Sync, const CAP: usize> Sync for AtomicStackVec<T, CAP> {}
+
+impl<T, const CAP: usize> AtomicStackVec<T, CAP> {
+    /// Creates a new empty AtomicStackVec
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            inner: [const { UnsafeCell::new(MaybeUninit::uninit()) }; CAP],
+            ready: [const { AtomicBool::new(false) }; CAP],
+            claimed: AtomicUsize::new(0),
+            committed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Attempts to push an element into the vector, returning its index.
+    ///
+    /// The element becomes visible to [as_slice](Self::as_slice) once
+    /// all the pushes that claimed a previous slot have finished.
+    ///
+    /// # Errors
+    /// If the vector is full, returns back the element
+    /// inside an Err variant.
+    pub fn push(&self, val: T) -> Result<usize, T> {
+        let claim = self
+            .claimed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                (n < CAP).then_some(n + 1)
+            });
+        let Ok(i) = claim else { return Err(val) };
+
+        unsafe {
+            /* SAFETY: We've claimed the ith slot, so no
+             * one else will access it until it's ready */
+            (*self.inner[i].get()).write(val);
+        }
+        /* The store and the loads in publish must be SeqCst. Otherwise,
+         * two pushers finishing adjacent slots could each miss the ready
+         * flag of the other, and none of them would publish its slot */
+        self.ready[i].store(true, Ordering::SeqCst);
+        self.publish();
+        Ok(i)
+    }
+
+    /// Advances the committed length over all the leading ready slots
+    fn publish(&self) {
+        let mut c = self.committed.load(Ordering::SeqCst);
+        while c < CAP && self.ready[c].load(Ordering::SeqCst) {
+            match self
+                .committed
+                .compare_exchange(c, c + 1, Ordering::SeqCst, Ordering::SeqCst)
+            {
+                Ok(_) => c += 1,
+                Err(actual) => c = actual,
+            }
+        }
+    }
+
+    /// Returns a snapshot of the elements pushed so far.
+    ///
+    /// Elements pushed after calling this function won't
+    /// be visible in the returned slice.
+    pub fn as_slice(&self) -> &[T] {
+        let len = self.committed.load(Ordering::Acquire);
+        /* SAFETY: The first len slots are initialized, and will
+         * never be modified again. UnsafeCell<MaybeUninit<T>>
+         * has the same memory layout as T */
+        unsafe { slice::from_raw_parts(self.inner.as_ptr() as *const T, len) }
+    }
+
+    /// Returns the number of elements visible to readers
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.committed.load(Ordering::Acquire)
+    }
+
+    /// Returns true if no elements are visible to readers
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if every slot has been claimed by a push
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.claimed.load(Ordering::Relaxed) == CAP
+    }
+
+    /// Returns the capacity of this AtomicStackVec
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for AtomicStackVec<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Drop for AtomicStackVec<T, CAP> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            for (slot, ready) in self.inner.iter_mut().zip(&mut self.ready) {
+                if *ready.get_mut() {
+                    /* SAFETY: The slot was initialized by a push */
+                    unsafe { slot.get_mut().assume_init_drop() };
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! A fixed-capacity priority queue
+//!
+//! See [StackBinaryHeap]
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{Deref, DerefMut};
+
+use crate::StackVec;
+
+/// A max-heap with a fixed capacity of CAP elements, backed by a [StackVec].
+///
+/// # Example
+/// ```
+/// use stack_vector::StackBinaryHeap;
+///
+/// let mut heap = StackBinaryHeap::<i32, 4>::new();
+/// heap.push(3).unwrap();
+/// heap.push(8).unwrap();
+/// heap.push(1).unwrap();
+///
+/// assert_eq!(heap.peek(), Some(&8));
+/// assert_eq!(heap.pop(), Some(8));
+/// assert_eq!(heap.into_sorted_vec().as_slice(), &[1, 3]);
+/// ```
+pub struct StackBinaryHeap<T, const CAP: usize> {
+    data: StackVec<T, CAP>,
+}
+
+impl<T, const CAP: usize> StackBinaryHeap<T, CAP> {
+    /// Creates a new empty StackBinaryHeap
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            data: StackVec::new(),
+        }
+    }
+
+    /// Returns a reference to the greatest element of the heap,
+    /// or None if it's empty
+    #[inline]
+    pub const fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the elements of the heap, in no particular order
+    #[inline]
+    pub const fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns an iterator over the elements of the heap,
+    /// in no particular order
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Consumes the heap, returning its elements in no particular order
+    #[inline]
+    pub fn into_vec(self) -> StackVec<T, CAP> {
+        self.data
+    }
+
+    /// Removes all the elements of the heap
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the capacity of this StackBinaryHeap
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of elements in the heap
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns true if the heap is empty
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns true if the heap is full
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+}
+
+impl<T: Ord, const CAP: usize> StackBinaryHeap<T, CAP> {
+    /// Moves the element at `i` up, until its parent is not smaller
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.data[i] <= self.data[parent] {
+                break;
+            }
+            self.data.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    /// Moves the element at `i` down, until none of its children
+    /// in `[0, end)` are greater
+    fn sift_down(&mut self, mut i: usize, end: usize) {
+        loop {
+            let mut child = 2 * i + 1;
+            if child >= end {
+                break;
+            }
+            if child + 1 < end && self.data[child + 1] > self.data[child] {
+                child += 1;
+            }
+            if self.data[i] >= self.data[child] {
+                break;
+            }
+            self.data.swap(i, child);
+            i = child;
+        }
+    }
+
+    /// Pushes an element into the heap.
+    ///
+    /// # Errors
+    /// If the heap is full, returns the element back
+    /// inside an Err variant.
+    pub fn push(&mut self, val: T) -> Result<(), T> {
+        self.data.try_push(val)?;
+        self.sift_up(self.len() - 1);
+        Ok(())
+    }
+
+    /// Removes the greatest element of the heap and returns it,
+    /// or None if it's empty
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        if self.data.is_empty() {
+            return Some(last);
+        }
+        let top = core::mem::replace(&mut self.data[0], last);
+        self.sift_down(0, self.len());
+        Some(top)
+    }
+
+    /// Returns a mutable reference to the greatest element of the heap,
+    /// or None if it's empty.
+    ///
+    /// The heap is restored when the returned [PeekMut] is dropped, so
+    /// the priority of the element can be changed in place.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackBinaryHeap;
+    ///
+    /// let mut heap = StackBinaryHeap::<i32, 4>::new();
+    /// heap.push(5).unwrap();
+    /// heap.push(2).unwrap();
+    ///
+    /// *heap.peek_mut().unwrap() = 1;
+    /// assert_eq!(heap.peek(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, CAP>> {
+        (!self.is_empty()).then_some(PeekMut {
+            heap: self,
+            changed: false,
+        })
+    }
+
+    /// Consumes the heap, returning its elements sorted in ascending order
+    pub fn into_sorted_vec(mut self) -> StackVec<T, CAP> {
+        let mut end = self.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    /// Returns an iterator that removes the elements of the
+    /// heap in descending order.
+    ///
+    /// If the iterator is dropped before being fully consumed,
+    /// the remaining elements are removed anyway.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackBinaryHeap;
+    ///
+    /// let mut heap = StackBinaryHeap::<i32, 4>::new();
+    /// heap.push(2).unwrap();
+    /// heap.push(7).unwrap();
+    /// heap.push(4).unwrap();
+    ///
+    /// let mut drain = heap.drain_sorted();
+    /// assert_eq!(drain.next(), Some(7));
+    /// assert_eq!(drain.next(), Some(4));
+    /// drop(drain);
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, CAP> {
+        DrainSorted { heap: self }
+    }
+}
+
+impl<T, const CAP: usize> Default for StackBinaryHeap<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const CAP: usize> fmt::Debug for StackBinaryHeap<T, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a StackBinaryHeap<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A mutable reference to the greatest element of a [StackBinaryHeap].
+///
+/// This struct is created by [StackBinaryHeap::peek_mut]. When it's
+/// dropped, the element is moved down to its place in the heap.
+pub struct PeekMut<'a, T: Ord, const CAP: usize> {
+    heap: &'a mut StackBinaryHeap<T, CAP>,
+    changed: bool,
+}
+
+impl<T: Ord, const CAP: usize> PeekMut<'_, T, CAP> {
+    /// Removes the peeked element from the heap, and returns it
+    #[inline]
+    pub fn pop(mut this: Self) -> T {
+        /* The element is about to be removed, no need to sift it down */
+        this.changed = false;
+        /* The heap is never empty while a PeekMut is alive */
+        this.heap.pop().unwrap()
+    }
+}
+
+impl<T: Ord, const CAP: usize> Deref for PeekMut<'_, T, CAP> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<T: Ord, const CAP: usize> DerefMut for PeekMut<'_, T, CAP> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.changed = true;
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T: Ord, const CAP: usize> Drop for PeekMut<'_, T, CAP> {
+    fn drop(&mut self) {
+        if self.changed {
+            let len = self.heap.len();
+            self.heap.sift_down(0, len);
+        }
+    }
+}
+
+/// A draining iterator over the elements of a [StackBinaryHeap],
+/// in descending order.
+///
+/// This struct is created by [StackBinaryHeap::drain_sorted].
+pub struct DrainSorted<'a, T: Ord, const CAP: usize> {
+    heap: &'a mut StackBinaryHeap<T, CAP>,
+}
+
+impl<T: Ord, const CAP: usize> Iterator for DrainSorted<'_, T, CAP> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: Ord, const CAP: usize> ExactSizeIterator for DrainSorted<'_, T, CAP> {}
+
+impl<T: Ord, const CAP: usize> FusedIterator for DrainSorted<'_, T, CAP> {}
+
+impl<T: Ord, const CAP: usize> Drop for DrainSorted<'_, T, CAP> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
@@ -0,0 +1,335 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use crate::CapacityError;
+
+type Word = u32;
+
+const WORD_BITS: usize = Word::BITS as usize;
+
+/// Returns the number of words a [StackBitSet] needs to store `bits` bits
+///
+/// # Example
+/// ```
+/// use stack_vector::bitset::words_for;
+///
+/// assert_eq!(words_for(1), 1);
+/// assert_eq!(words_for(32), 1);
+/// assert_eq!(words_for(33), 2);
+/// ```
+pub const fn words_for(bits: usize) -> usize {
+    bits.div_ceil(WORD_BITS)
+}
+
+/// A set of BITS bits, stored inline in an array of WORDS 32-bit words.
+///
+/// Since stable rust doesn't allow to compute the length of the array
+/// from BITS, the number of words must be given explicitly. The
+/// [words_for] function can be used for this. Using less words than
+/// needed to store BITS bits is a compile time error.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackBitSet;
+/// use stack_vector::bitset::words_for;
+///
+/// let mut slots = StackBitSet::<40, { words_for(40) }>::new();
+///
+/// slots.set(3);
+/// slots.set(39);
+///
+/// assert!(slots.test(3));
+/// assert!(!slots.test(4));
+/// assert_eq!(slots.count_ones(), 2);
+///
+/// assert!(slots.try_set(40).is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StackBitSet<const BITS: usize, const WORDS: usize> {
+    words: [Word; WORDS],
+}
+
+impl<const BITS: usize, const WORDS: usize> StackBitSet<BITS, WORDS> {
+    /// Creates a new StackBitSet, with all the bits cleared
+    #[inline]
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                WORDS * WORD_BITS >= BITS,
+                "Not enough words to store BITS bits"
+            )
+        };
+        Self { words: [0; WORDS] }
+    }
+
+    #[inline(always)]
+    const fn locate(i: usize) -> (usize, Word) {
+        (i / WORD_BITS, 1 << (i % WORD_BITS))
+    }
+
+    /// Sets the ith bit, panicking if it's out of bounds
+    ///
+    /// # Panics
+    /// - If i >= BITS
+    #[inline]
+    pub fn set(&mut self, i: usize) {
+        if self.try_set(i).is_err() {
+            panic!("Attemp to set a bit beyond the capacity of the bitset")
+        }
+    }
+
+    /// Attempts to set the ith bit.
+    ///
+    /// # Errors
+    /// If i >= BITS
+    pub fn try_set(&mut self, i: usize) -> Result<(), CapacityError> {
+        if i >= BITS {
+            return Err(CapacityError);
+        }
+        let (w, mask) = Self::locate(i);
+        self.words[w] |= mask;
+        Ok(())
+    }
+
+    /// Clears the ith bit, panicking if it's out of bounds
+    ///
+    /// # Panics
+    /// - If i >= BITS
+    #[inline]
+    pub fn clear(&mut self, i: usize) {
+        if self.try_clear(i).is_err() {
+            panic!("Attemp to clear a bit beyond the capacity of the bitset")
+        }
+    }
+
+    /// Attempts to clear the ith bit.
+    ///
+    /// # Errors
+    /// If i >= BITS
+    pub fn try_clear(&mut self, i: usize) -> Result<(), CapacityError> {
+        if i >= BITS {
+            return Err(CapacityError);
+        }
+        let (w, mask) = Self::locate(i);
+        self.words[w] &= !mask;
+        Ok(())
+    }
+
+    /// Returns true if the ith bit is set.
+    /// If i is out of bounds, returns false.
+    pub const fn test(&self, i: usize) -> bool {
+        if i >= BITS {
+            return false;
+        }
+        let (w, mask) = Self::locate(i);
+        self.words[w] & mask != 0
+    }
+
+    /// Clears all the bits of this bitset
+    #[inline]
+    pub fn clear_all(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    /// Returns the number of bits set
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns the number of bits cleared
+    #[inline]
+    pub fn count_zeros(&self) -> usize {
+        BITS - self.count_ones()
+    }
+
+    /// Returns true if no bit is set
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// Returns true if all the bits are set
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.count_ones() == BITS
+    }
+
+    /// Returns a mask with the bits of the ith word that are within bounds
+    const fn valid_mask(i: usize) -> Word {
+        let start = i * WORD_BITS;
+        if start + WORD_BITS <= BITS {
+            Word::MAX
+        } else if start >= BITS {
+            0
+        } else {
+            (1 << (BITS - start)) - 1
+        }
+    }
+
+    /// Returns an iterator over the indices of the bits that are set
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackBitSet;
+    ///
+    /// let mut bs = StackBitSet::<64, 2>::new();
+    /// bs.set(1);
+    /// bs.set(40);
+    ///
+    /// assert!(bs.iter_ones().eq([1, 40]));
+    /// assert!(bs.iter_zeros().take(3).eq([0, 2, 3]));
+    /// ```
+    #[inline]
+    pub fn iter_ones(&self) -> IterOnes<'_, BITS, WORDS> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator over the indices of the bits that are cleared
+    #[inline]
+    pub fn iter_zeros(&self) -> IterZeros<'_, BITS, WORDS> {
+        Iter::new(self)
+    }
+
+    /// Returns the index of the first bit that is set
+    #[inline]
+    pub fn first_one(&self) -> Option<usize> {
+        self.iter_ones().next()
+    }
+
+    /// Returns the index of the first bit that is cleared.
+    ///
+    /// This is handy to find a free slot in an allocation bitmap.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackBitSet;
+    ///
+    /// let mut used = StackBitSet::<4, 1>::new();
+    ///
+    /// while let Some(slot) = used.first_zero() {
+    ///     used.set(slot);
+    /// }
+    /// assert!(used.is_full());
+    /// ```
+    #[inline]
+    pub fn first_zero(&self) -> Option<usize> {
+        self.iter_zeros().next()
+    }
+
+    /// Returns the number of bits in this bitset.
+    /// This is just a convenience function, since the
+    /// capacity is a const generic argument.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        BITS
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> Default for StackBitSet<BITS, WORDS> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> fmt::Debug for StackBitSet<BITS, WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter_ones()).finish()
+    }
+}
+
+macro_rules! impl_bitop {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl<const BITS: usize, const WORDS: usize> $assign_trait for StackBitSet<BITS, WORDS> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                for (l, r) in self.words.iter_mut().zip(rhs.words) {
+                    *l = *l $op r;
+                }
+            }
+        }
+
+        impl<const BITS: usize, const WORDS: usize> $trait for StackBitSet<BITS, WORDS> {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(mut self, rhs: Self) -> Self {
+                self.$assign_fn(rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_bitop!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bitop!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_bitop!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl<const BITS: usize, const WORDS: usize> Not for StackBitSet<BITS, WORDS> {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        for (i, w) in self.words.iter_mut().enumerate() {
+            /* Out of bounds bits must remain cleared */
+            *w = !*w & Self::valid_mask(i);
+        }
+        self
+    }
+}
+
+/// Iterator over the indices of the set (if ONES is true)
+/// or cleared (if ONES is false) bits of a [StackBitSet]
+pub struct Iter<'a, const BITS: usize, const WORDS: usize, const ONES: bool> {
+    set: &'a StackBitSet<BITS, WORDS>,
+    word: usize,
+    current: Word,
+}
+
+/// Iterator returned by [StackBitSet::iter_ones]
+pub type IterOnes<'a, const BITS: usize, const WORDS: usize> = Iter<'a, BITS, WORDS, true>;
+
+/// Iterator returned by [StackBitSet::iter_zeros]
+pub type IterZeros<'a, const BITS: usize, const WORDS: usize> = Iter<'a, BITS, WORDS, false>;
+
+impl<'a, const BITS: usize, const WORDS: usize, const ONES: bool> Iter<'a, BITS, WORDS, ONES> {
+    fn new(set: &'a StackBitSet<BITS, WORDS>) -> Self {
+        let mut it = Self {
+            set,
+            word: 0,
+            current: 0,
+        };
+        if WORDS > 0 {
+            it.current = it.load(0);
+        }
+        it
+    }
+
+    fn load(&self, i: usize) -> Word {
+        let w = self.set.words[i];
+        let w = if ONES { w } else { !w };
+        w & StackBitSet::<BITS, WORDS>::valid_mask(i)
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize, const ONES: bool> Iterator
+    for Iter<'_, BITS, WORDS, ONES>
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.word += 1;
+            if self.word >= WORDS {
+                return None;
+            }
+            self.current = self.load(self.word);
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.word * WORD_BITS + bit)
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize, const ONES: bool> FusedIterator
+    for Iter<'_, BITS, WORDS, ONES>
+{
+}
@@ -0,0 +1,54 @@
+//! Runtime detection of types whose clone is a bitwise copy
+//!
+//! Stable Rust can't specialize on `T: Copy`. Instead, we compare the
+//! TypeId of T against a list of primitive types, whose Clone impls are
+//! known to be plain copies. That way, generic code can take a memcpy
+//! path for them, and fall back to calling clone for any other type.
+
+use core::any::TypeId;
+use core::marker::PhantomData;
+use core::mem;
+
+trait NonStaticAny {
+    fn get_type_id(&self) -> TypeId
+    where
+        Self: 'static;
+}
+
+impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+    fn get_type_id(&self) -> TypeId
+    where
+        Self: 'static,
+    {
+        TypeId::of::<T>()
+    }
+}
+
+/// Returns the TypeId of T, which doesn't need to be 'static.
+///
+/// Lifetimes are erased before the TypeId is computed, so this must
+/// only be used to compare against types without lifetimes.
+fn type_id<T: ?Sized>() -> TypeId {
+    let phantom = PhantomData::<T>;
+    let any: &dyn NonStaticAny = &phantom;
+    /* SAFETY: This only changes the lifetime bound of the trait object.
+     * PhantomData<T> holds no data, so nothing can be accessed through
+     * the extended lifetime */
+    let any: &(dyn NonStaticAny + 'static) = unsafe { mem::transmute(any) };
+    any.get_type_id()
+}
+
+/// Returns true if T is a primitive type whose
+/// [Clone] impl just copies its bytes
+pub(crate) fn is_bitwise_clone<T>() -> bool {
+    macro_rules! any_of {
+        ($($ty:ty),+) => {{
+            let id = type_id::<T>();
+            $(id == TypeId::of::<$ty>())||+
+        }};
+    }
+
+    any_of!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char
+    )
+}
@@ -0,0 +1,152 @@
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ptr;
+
+use crate::StackVec;
+
+/// A builder for arrays of CAP elements, that tracks the
+/// number of pushed elements (LEN) in its type.
+///
+/// Every [push](Self::push) returns a builder with LEN + 1, and
+/// [finish](Self::finish) only compiles when LEN == CAP. This gives
+/// compile-time guarantees when building fixed layouts, like protocol
+/// headers, where forgetting (or adding an extra) field should be a
+/// build error instead of a runtime one.
+///
+/// Since stable Rust can't compute LEN + 1 in a type, the push method
+/// is implemented for LEN up to 63, so CAP can be at most 64.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackVecBuilder;
+///
+/// let header: [u8; 4] = StackVecBuilder::new()
+///     .push(0x7F)
+///     .push(b'E')
+///     .push(b'L')
+///     .push(b'F')
+///     .finish();
+///
+/// assert_eq!(&header, b"\x7FELF");
+/// ```
+///
+/// Finishing before filling all the elements doesn't compile
+/// ```compile_fail
+/// use stack_vector::StackVecBuilder;
+///
+/// let header: [u8; 4] = StackVecBuilder::new().push(1).push(2).finish();
+/// ```
+///
+/// And neither does pushing too many
+/// ```compile_fail
+/// use stack_vector::StackVecBuilder;
+///
+/// let header = StackVecBuilder::<u8, 1, 0>::new().push(1).push(2);
+/// ```
+pub struct StackVecBuilder<T, const CAP: usize, const LEN: usize> {
+    inner: [MaybeUninit<T>; CAP],
+}
+
+impl<T, const CAP: usize> StackVecBuilder<T, CAP, 0> {
+    /// Creates a new empty StackVecBuilder
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            inner: [const { MaybeUninit::uninit() }; CAP],
+        }
+    }
+}
+
+impl<T, const CAP: usize, const LEN: usize> StackVecBuilder<T, CAP, LEN> {
+    /// Returns the number of elements pushed so far
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        LEN
+    }
+
+    /// Returns true if no elements have been pushed yet
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        LEN == 0
+    }
+
+    /// Returns the elements pushed so far
+    #[inline]
+    pub const fn as_slice(&self) -> &[T] {
+        let inner = self.inner.as_slice().split_at(LEN).0;
+        /* SAFETY: The first LEN elements are initialized */
+        unsafe { mem::transmute::<&[MaybeUninit<T>], &[T]>(inner) }
+    }
+
+    /// Takes the buffer out of this builder, without dropping its elements
+    #[inline]
+    fn into_inner(self) -> [MaybeUninit<T>; CAP] {
+        let this = ManuallyDrop::new(self);
+        /* SAFETY: self won't be dropped, so the elements are moved */
+        unsafe { ptr::read(&this.inner) }
+    }
+
+    /// Returns the array, with all its elements.
+    ///
+    /// This method only compiles when LEN == CAP
+    #[inline]
+    pub fn finish(self) -> [T; CAP] {
+        const { assert!(LEN == CAP, "The builder is not full") }
+        let inner = ManuallyDrop::new(self.into_inner());
+        /* SAFETY: All the CAP elements are initialized, and
+         * [MaybeUninit<T>; CAP] has the same layout as [T; CAP] */
+        unsafe { mem::transmute_copy(&inner) }
+    }
+
+    /// Converts this builder into a [StackVec] with the
+    /// elements pushed so far
+    #[inline]
+    pub fn into_stack_vec(self) -> StackVec<T, CAP> {
+        let mut sv = StackVec::new();
+        sv.inner = self.into_inner();
+        /* SAFETY: The first LEN elements are initialized */
+        unsafe { sv.set_len(LEN) };
+        sv
+    }
+}
+
+macro_rules! impl_push {
+    ($len:literal $next:literal $($rest:literal)*) => {
+        impl<T, const CAP: usize> StackVecBuilder<T, CAP, $len> {
+            /// Pushes an element, returning a builder
+            /// that has room for one less element.
+            ///
+            /// This method only compiles when LEN < CAP
+            #[inline]
+            pub fn push(self, val: T) -> StackVecBuilder<T, CAP, $next> {
+                const { assert!($len < CAP, "The builder is full") }
+                let mut inner = self.into_inner();
+                inner[$len].write(val);
+                StackVecBuilder { inner }
+            }
+        }
+
+        impl_push!($next $($rest)*);
+    };
+    ($len:literal) => {};
+}
+
+impl_push!(
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+    33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62
+    63 64
+);
+
+impl<T, const CAP: usize> Default for StackVecBuilder<T, CAP, 0> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize, const LEN: usize> Drop for StackVecBuilder<T, CAP, LEN> {
+    fn drop(&mut self) {
+        let init = ptr::slice_from_raw_parts_mut(self.inner.as_mut_ptr().cast::<T>(), LEN);
+        /* SAFETY: The first LEN elements are initialized */
+        unsafe { ptr::drop_in_place(init) };
+    }
+}
@@ -0,0 +1,187 @@
+//! A bump allocator backed by a fixed buffer
+//!
+//! See [StackBumpAlloc]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A bump allocator that hands out memory from an inline buffer of CAP bytes.
+///
+/// It implements [GlobalAlloc], so it can be registered with
+/// `#[global_allocator]`, allowing dependencies that need `alloc`
+/// to run on targets without a heap, while all the memory still
+/// lives in a fixed region.
+///
+/// Allocating just moves a pointer forward. Memory is only given back
+/// when the most recent allocation is freed (or shrunk), so this is best
+/// suited for programs that allocate mostly at startup, or in a LIFO way.
+/// Growing the most recent allocation is done in place.
+///
+/// # Example
+/// ```
+/// use core::alloc::{GlobalAlloc, Layout};
+/// use stack_vector::StackBumpAlloc;
+///
+/// static ALLOC: StackBumpAlloc<64> = StackBumpAlloc::new();
+///
+/// let layout = Layout::new::<u64>();
+/// let a = unsafe { ALLOC.alloc(layout) };
+/// let b = unsafe { ALLOC.alloc(layout) };
+/// assert!(!a.is_null() && !b.is_null());
+/// assert_eq!(a as usize % 8, 0);
+///
+/// /* b is the most recent allocation, so its memory is reclaimed */
+/// let used = ALLOC.used();
+/// unsafe { ALLOC.dealloc(b, layout) };
+/// assert_eq!(ALLOC.used(), used - 8);
+///
+/// /* There's no room for this one */
+/// let big = Layout::from_size_align(128, 1).unwrap();
+/// assert!(unsafe { ALLOC.alloc(big) }.is_null());
+/// ```
+///
+/// To use it as the global allocator:
+/// ```no_run
+/// use stack_vector::StackBumpAlloc;
+///
+/// #[global_allocator]
+/// static HEAP: StackBumpAlloc<{ 64 * 1024 }> = StackBumpAlloc::new();
+/// ```
+pub struct StackBumpAlloc<const CAP: usize> {
+    buf: UnsafeCell<[MaybeUninit<u8>; CAP]>,
+    /* Offset of the first free byte in buf */
+    next: AtomicUsize,
+}
+
+/* SAFETY: Regions of the buffer are claimed with atomic operations,
+ * so each one is handed out to a single owner at a time */
+unsafe impl<const CAP: usize> Sync for StackBumpAlloc<CAP> {}
+
+impl<const CAP: usize> StackBumpAlloc<CAP> {
+    /// Creates a new StackBumpAlloc, with all its memory available
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: UnsafeCell::new([MaybeUninit::uninit(); CAP]),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline(always)]
+    fn base(&self) -> *mut u8 {
+        self.buf.get().cast()
+    }
+
+    /// Returns the offset of the given pointer, relative to the buffer
+    #[inline(always)]
+    fn offset_of(&self, ptr: *mut u8) -> usize {
+        ptr as usize - self.base() as usize
+    }
+
+    /// Returns the region that an allocation with the given
+    /// layout would take, if the free memory starts at `next`
+    fn region(&self, next: usize, layout: Layout) -> Option<(usize, usize)> {
+        let base = self.base() as usize;
+        let start = base
+            .checked_add(next)?
+            .checked_next_multiple_of(layout.align())?
+            - base;
+        let end = start.checked_add(layout.size())?;
+        (end <= CAP).then_some((start, end))
+    }
+
+    /// Returns the number of bytes in use, including
+    /// the padding added to align the allocations
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.next.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes that haven't been handed out yet
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        CAP - self.used()
+    }
+
+    /// Returns the capacity of this allocator, in bytes
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Makes all the memory available again.
+    ///
+    /// Any pointer returned by this allocator before
+    /// the reset must not be used after it.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self.next.get_mut() = 0;
+    }
+}
+
+impl<const CAP: usize> Default for StackBumpAlloc<CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<const CAP: usize> GlobalAlloc for StackBumpAlloc<CAP> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let mut start = 0;
+        let claim = self
+            .next
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |next| {
+                let (s, end) = self.region(next, layout)?;
+                start = s;
+                Some(end)
+            });
+        match claim {
+            /* SAFETY: The region is inside the buffer */
+            Ok(_) => unsafe { self.base().add(start) },
+            Err(_) => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let start = self.offset_of(ptr);
+        let end = start + layout.size();
+        /* Only the most recent allocation can be given back */
+        let _ = self
+            .next
+            .compare_exchange(end, start, Ordering::AcqRel, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let start = self.offset_of(ptr);
+        let end = start + layout.size();
+        if let Some(new_end) = start.checked_add(new_size) {
+            /* If this is the most recent allocation, resize it in place */
+            if new_end <= CAP
+                && self
+                    .next
+                    .compare_exchange(end, new_end, Ordering::AcqRel, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return ptr;
+            }
+        }
+
+        /* SAFETY: The caller guarantees that new_size, rounded
+         * up to the alignment, doesn't overflow isize */
+        let new_layout = unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) };
+        let new = unsafe { self.alloc(new_layout) };
+        if !new.is_null() {
+            unsafe {
+                /* SAFETY: Both regions are valid for the smallest of
+                 * the sizes, and a live allocation can't overlap a new one */
+                ptr::copy_nonoverlapping(ptr, new, layout.size().min(new_size));
+                self.dealloc(ptr, layout);
+            }
+        }
+        new
+    }
+}
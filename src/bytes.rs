@@ -0,0 +1,293 @@
+//! Helpers to encode and decode binary data in a [StackVec] of bytes
+//!
+//! # Example
+//! ```
+//! use stack_vector::StackVec;
+//! use stack_vector::bytes::StackCursor;
+//!
+//! let frame = StackVec::from_array([0x01, 0x34, 0x12, b'h', b'i']);
+//! let mut cur = StackCursor::new(&frame);
+//!
+//! assert_eq!(cur.read_u8(), Ok(1));
+//! assert_eq!(cur.read_u16_le(), Ok(0x1234));
+//! assert_eq!(cur.read_bytes(2), Ok(&b"hi"[..]));
+//! assert!(cur.read_u8().is_err());
+//! ```
+
+use core::fmt;
+
+use crate::{CapacityError, StackVec};
+
+/// Error returned when there are not enough bytes left to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnderrunError;
+
+impl fmt::Display for UnderrunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not enough bytes left")
+    }
+}
+
+impl core::error::Error for UnderrunError {}
+
+/// Error returned when decoding an invalid varint
+#[cfg(feature = "varint")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarintError {
+    /// The buffer ended before the last byte of the varint
+    Underrun,
+    /// The varint doesn't fit in the target integer type
+    Overflow,
+}
+
+#[cfg(feature = "varint")]
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarintError::Underrun => f.write_str("unterminated varint"),
+            VarintError::Overflow => f.write_str("varint overflows the integer type"),
+        }
+    }
+}
+
+#[cfg(feature = "varint")]
+impl core::error::Error for VarintError {}
+
+/// A cursor that reads bytes from a [StackVec].
+///
+/// When a read fails because there are not enough bytes
+/// left, the position of the cursor is left unchanged.
+pub struct StackCursor<'a, const CAP: usize> {
+    buf: &'a StackVec<u8, CAP>,
+    pos: usize,
+}
+
+macro_rules! impl_read {
+    ($($ty:ident => $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Reads a little endian ", stringify!($ty))]
+            #[inline]
+            pub fn $le(&mut self) -> Result<$ty, UnderrunError> {
+                self.read_array().map($ty::from_le_bytes)
+            }
+
+            #[doc = concat!("Reads a big endian ", stringify!($ty))]
+            #[inline]
+            pub fn $be(&mut self) -> Result<$ty, UnderrunError> {
+                self.read_array().map($ty::from_be_bytes)
+            }
+        )*
+    };
+}
+
+impl<'a, const CAP: usize> StackCursor<'a, CAP> {
+    /// Creates a cursor at the start of the given StackVec
+    #[inline]
+    pub const fn new(buf: &'a StackVec<u8, CAP>) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Returns the position of the cursor
+    #[inline(always)]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Moves the cursor to the given position.
+    ///
+    /// # Errors
+    /// If the position is past the end of the buffer
+    pub fn set_position(&mut self, pos: usize) -> Result<(), UnderrunError> {
+        if pos > self.buf.len() {
+            return Err(UnderrunError);
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    /// Returns the number of bytes left to read
+    #[inline(always)]
+    pub const fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Returns the bytes left to read, without advancing the cursor
+    #[inline]
+    pub fn remaining_slice(&self) -> &'a [u8] {
+        &self.buf.as_slice()[self.pos..]
+    }
+
+    /// Reads the next `n` bytes
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], UnderrunError> {
+        let bytes = self.remaining_slice().get(..n).ok_or(UnderrunError)?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    /// Advances the cursor `n` bytes
+    #[inline]
+    pub fn skip(&mut self, n: usize) -> Result<(), UnderrunError> {
+        self.read_bytes(n).map(|_| ())
+    }
+
+    /// Reads the next N bytes, as an array
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], UnderrunError> {
+        let bytes = self.read_bytes(N)?;
+        let mut arr = [0; N];
+        arr.copy_from_slice(bytes);
+        Ok(arr)
+    }
+
+    /// Reads a byte
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, UnderrunError> {
+        self.read_array().map(|[b]| b)
+    }
+
+    impl_read! {
+        u16 => read_u16_le, read_u16_be;
+        u32 => read_u32_le, read_u32_be;
+        u64 => read_u64_le, read_u64_be;
+    }
+}
+
+/// Decoding of unsigned LEB128 varints
+///
+/// # Example
+/// ```
+/// use stack_vector::StackVec;
+/// use stack_vector::bytes::StackCursor;
+///
+/// let mut buf = StackVec::<u8, 8>::new();
+/// buf.put_varint_u64(300).unwrap();
+/// assert_eq!(buf.as_slice(), &[0xAC, 0x02]);
+///
+/// let mut cur = StackCursor::new(&buf);
+/// assert_eq!(cur.read_varint_u64(), Ok(300));
+/// ```
+#[cfg(feature = "varint")]
+impl<const CAP: usize> StackCursor<'_, CAP> {
+    /// Reads an unsigned LEB128 varint, of at most 10 bytes
+    ///
+    /// # Errors
+    /// - [VarintError::Underrun] if the buffer ends before the varint
+    /// - [VarintError::Overflow] if the varint doesn't fit in a u64
+    pub fn read_varint_u64(&mut self) -> Result<u64, VarintError> {
+        let mut n = 0_u64;
+        for (i, &b) in self.remaining_slice().iter().enumerate() {
+            let bits = u64::from(b & 0x7F);
+            if i == 9 && bits > 1 {
+                return Err(VarintError::Overflow);
+            }
+            n |= bits << (7 * i);
+            if b & 0x80 == 0 {
+                self.pos += i + 1;
+                return Ok(n);
+            }
+            if i == 9 {
+                return Err(VarintError::Overflow);
+            }
+        }
+        Err(VarintError::Underrun)
+    }
+
+    /// Reads an unsigned LEB128 varint, that must fit in a u32
+    ///
+    /// # Errors
+    /// - [VarintError::Underrun] if the buffer ends before the varint
+    /// - [VarintError::Overflow] if the varint doesn't fit in a u32
+    pub fn read_varint_u32(&mut self) -> Result<u32, VarintError> {
+        let pos = self.pos;
+        let n = self.read_varint_u64()?;
+        u32::try_from(n).map_err(|_| {
+            self.pos = pos;
+            VarintError::Overflow
+        })
+    }
+}
+
+macro_rules! impl_put {
+    ($($ty:ident => $le:ident, $be:ident;)*) => {
+        $(
+            #[doc = concat!("Appends a little endian ", stringify!($ty))]
+            #[inline]
+            pub fn $le(&mut self, n: $ty) -> Result<(), CapacityError> {
+                self.put_slice(&n.to_le_bytes())
+            }
+
+            #[doc = concat!("Appends a big endian ", stringify!($ty))]
+            #[inline]
+            pub fn $be(&mut self, n: $ty) -> Result<(), CapacityError> {
+                self.put_slice(&n.to_be_bytes())
+            }
+        )*
+    };
+}
+
+/// Functions to write binary data.
+///
+/// If a value doesn't fit, nothing is written,
+/// and a [CapacityError] is returned.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackVec;
+///
+/// let mut frame = StackVec::<u8, 8>::new();
+/// frame.put_u8(0x01).unwrap();
+/// frame.put_u16_le(0x1234).unwrap();
+/// frame.put_slice(b"hi").unwrap();
+///
+/// assert_eq!(frame.as_slice(), &[0x01, 0x34, 0x12, b'h', b'i']);
+/// assert!(frame.put_u32_be(0).is_err());
+/// ```
+impl<const CAP: usize> StackVec<u8, CAP> {
+    /// Appends the given bytes
+    pub fn put_slice(&mut self, bytes: &[u8]) -> Result<(), CapacityError> {
+        self.ensure_remaining(bytes.len())?;
+        for &b in bytes {
+            /* SAFETY: We've just checked that there's room for the bytes */
+            unsafe { self.push_unchecked(b) };
+        }
+        Ok(())
+    }
+
+    /// Appends a byte
+    #[inline]
+    pub fn put_u8(&mut self, n: u8) -> Result<(), CapacityError> {
+        self.put_slice(&[n])
+    }
+
+    impl_put! {
+        u16 => put_u16_le, put_u16_be;
+        u32 => put_u32_le, put_u32_be;
+        u64 => put_u64_le, put_u64_be;
+    }
+
+    /// Appends an unsigned LEB128 varint
+    #[cfg(feature = "varint")]
+    pub fn put_varint_u64(&mut self, mut n: u64) -> Result<(), CapacityError> {
+        let mut buf = [0; 10];
+        let mut len = 0;
+        loop {
+            let b = (n & 0x7F) as u8;
+            n >>= 7;
+            if n == 0 {
+                buf[len] = b;
+                len += 1;
+                break;
+            }
+            buf[len] = b | 0x80;
+            len += 1;
+        }
+        self.put_slice(&buf[..len])
+    }
+
+    /// Appends an unsigned LEB128 varint
+    #[cfg(feature = "varint")]
+    #[inline]
+    pub fn put_varint_u32(&mut self, n: u32) -> Result<(), CapacityError> {
+        self.put_varint_u64(n.into())
+    }
+}
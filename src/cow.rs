@@ -0,0 +1,135 @@
+use core::ops::Deref;
+
+use crate::{CapacityError, StackVec};
+
+/// A clone-on-write slice, that is either borrowed, or owned in a [StackVec].
+///
+/// This allows APIs to take read-only inputs without copying them,
+/// while still being able to modify them in place when needed.
+/// The first mutable access through [to_mut](Self::to_mut) copies the
+/// borrowed slice into the inline buffer.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackCow;
+///
+/// fn normalize(input: &[i32]) -> StackCow<'_, i32, 8> {
+///     let mut cow = StackCow::from(input);
+///     for i in 0..cow.len() {
+///         if cow[i] < 0 {
+///             cow.to_mut().unwrap()[i] = 0;
+///         }
+///     }
+///     cow
+/// }
+///
+/// let clean = normalize(&[1, 2, 3]);
+/// assert!(clean.is_borrowed());
+///
+/// let dirty = normalize(&[1, -2, 3]);
+/// assert!(dirty.is_owned());
+/// assert_eq!(&*dirty, &[1, 0, 3]);
+/// ```
+pub enum StackCow<'a, T, const CAP: usize> {
+    Borrowed(&'a [T]),
+    Owned(StackVec<T, CAP>),
+}
+
+impl<'a, T, const CAP: usize> StackCow<'a, T, CAP> {
+    /// Returns the contents of this StackCow as a slice
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            StackCow::Borrowed(s) => s,
+            StackCow::Owned(sv) => sv.as_slice(),
+        }
+    }
+
+    /// Returns true if this StackCow is borrowed
+    #[inline]
+    pub const fn is_borrowed(&self) -> bool {
+        matches!(self, StackCow::Borrowed(_))
+    }
+
+    /// Returns true if this StackCow is owned
+    #[inline]
+    pub const fn is_owned(&self) -> bool {
+        !self.is_borrowed()
+    }
+
+    /// Returns a mutable reference to the owned StackVec, copying
+    /// the borrowed slice into it first if needed.
+    ///
+    /// # Errors
+    /// If the StackCow is borrowed, and the slice
+    /// doesn't fit in CAP elements
+    pub fn to_mut(&mut self) -> Result<&mut StackVec<T, CAP>, CapacityError>
+    where
+        T: Clone,
+    {
+        if let StackCow::Borrowed(s) = *self {
+            *self = StackCow::Owned(Self::clone_slice(s)?);
+        }
+        match self {
+            StackCow::Owned(sv) => Ok(sv),
+            StackCow::Borrowed(_) => unreachable!(),
+        }
+    }
+
+    /// Converts this StackCow into an owned StackVec, copying
+    /// the borrowed slice into it if needed.
+    ///
+    /// # Errors
+    /// If the StackCow is borrowed, and the slice
+    /// doesn't fit in CAP elements
+    pub fn into_owned(self) -> Result<StackVec<T, CAP>, CapacityError>
+    where
+        T: Clone,
+    {
+        match self {
+            StackCow::Borrowed(s) => Self::clone_slice(s),
+            StackCow::Owned(sv) => Ok(sv),
+        }
+    }
+
+    fn clone_slice(s: &[T]) -> Result<StackVec<T, CAP>, CapacityError>
+    where
+        T: Clone,
+    {
+        let mut sv = StackVec::new();
+        sv.extend_cloned(s)?;
+        Ok(sv)
+    }
+}
+
+impl<T, const CAP: usize> Deref for StackCow<'_, T, CAP> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<'a, T, const CAP: usize> From<&'a [T]> for StackCow<'a, T, CAP> {
+    #[inline]
+    fn from(s: &'a [T]) -> Self {
+        StackCow::Borrowed(s)
+    }
+}
+
+impl<T, const CAP: usize> From<StackVec<T, CAP>> for StackCow<'_, T, CAP> {
+    #[inline]
+    fn from(sv: StackVec<T, CAP>) -> Self {
+        StackCow::Owned(sv)
+    }
+}
+
+impl<T: PartialEq, const CAP: usize> PartialEq for StackCow<'_, T, CAP> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const CAP: usize> Eq for StackCow<'_, T, CAP> {}
@@ -0,0 +1,187 @@
+use core::ptr;
+
+use crate::StackVec;
+
+/// A cursor over a [StackVec], that allows to edit it while traversing it.
+///
+/// This struct is created by [StackVec::cursor_mut].
+///
+/// While the cursor is alive, the elements before the cursor are kept
+/// at the start of the buffer, and the rest of them at the end of it.
+/// This way, removing or inserting elements at the cursor is O(1),
+/// and the elements are moved back into place only once, when the
+/// cursor is dropped.
+///
+/// If the cursor is leaked, the elements of the StackVec are leaked too.
+pub struct CursorMut<'a, T, const CAP: usize> {
+    sv: &'a mut StackVec<T, CAP>,
+    /* Elements in [0, front) are before the cursor */
+    front: usize,
+    /* Elements in [CAP - back, CAP) are at and after the cursor */
+    back: usize,
+}
+
+impl<'a, T, const CAP: usize> CursorMut<'a, T, CAP> {
+    pub(super) fn new(sv: &'a mut StackVec<T, CAP>) -> Self {
+        let len = sv.length;
+        let base = sv.as_mut_ptr();
+        unsafe {
+            /* SAFETY: Move all the elements to the end of the buffer */
+            ptr::copy(base, base.add(CAP - len), len);
+        }
+        sv.length = 0;
+        Self {
+            sv,
+            front: 0,
+            back: len,
+        }
+    }
+
+    #[inline(always)]
+    fn base(&mut self) -> *mut T {
+        self.sv.as_mut_ptr()
+    }
+
+    #[inline(always)]
+    fn base_const(&self) -> *const T {
+        self.sv.as_ptr()
+    }
+
+    /// Returns the index of the current element
+    #[inline(always)]
+    pub fn index(&self) -> usize {
+        self.front
+    }
+
+    /// Returns the total number of elements
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.front + self.back
+    }
+
+    /// Returns true if there are no elements
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a mutable reference to the current element, or
+    /// None if the cursor is past the end of the StackVec.
+    pub fn current(&mut self) -> Option<&mut T> {
+        if self.back == 0 {
+            return None;
+        }
+        let back = self.back;
+        /* SAFETY: The back part has at least one element */
+        Some(unsafe { &mut *self.base().add(CAP - back) })
+    }
+
+    /// Returns a reference to the element after the current one
+    pub fn peek_next(&self) -> Option<&T> {
+        if self.back < 2 {
+            return None;
+        }
+        /* SAFETY: The back part has at least two elements */
+        Some(unsafe { &*self.base_const().add(CAP - self.back + 1) })
+    }
+
+    /// Returns a reference to the element before the current one
+    pub fn peek_prev(&self) -> Option<&T> {
+        if self.front == 0 {
+            return None;
+        }
+        /* SAFETY: The front part has at least one element */
+        Some(unsafe { &*self.base_const().add(self.front - 1) })
+    }
+
+    /// Moves the cursor to the next element.
+    /// Returns false if the cursor was already past the end.
+    pub fn move_next(&mut self) -> bool {
+        if self.back == 0 {
+            return false;
+        }
+        let (front, back) = (self.front, self.back);
+        let base = self.base();
+        unsafe {
+            /* SAFETY: The gap between both parts has room for the element */
+            ptr::copy(base.add(CAP - back), base.add(front), 1);
+        }
+        self.front += 1;
+        self.back -= 1;
+        true
+    }
+
+    /// Removes the current element, and returns it.
+    /// The cursor then points to the next element.
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.back == 0 {
+            return None;
+        }
+        let back = self.back;
+        self.back -= 1;
+        /* SAFETY: The element is initialized, and it's no longer in the back part */
+        let elem = unsafe { ptr::read(self.base().add(CAP - back)) };
+        self.sv.poison(CAP - back, CAP - back + 1);
+        Some(elem)
+    }
+
+    /// Inserts an element before the current one.
+    /// The cursor keeps pointing to the same element.
+    ///
+    /// # Errors
+    /// If the StackVec is full, returns back the element
+    /// inside an Err variant.
+    pub fn insert_before(&mut self, val: T) -> Result<(), T> {
+        if self.len() == CAP {
+            self.sv.overflow("insert_before");
+            return Err(val);
+        }
+        let front = self.front;
+        /* SAFETY: The gap between both parts has room for the element */
+        unsafe { self.base().add(front).write(val) };
+        self.front += 1;
+        Ok(())
+    }
+
+    /// Inserts an element after the current one. If the cursor
+    /// is past the end, the inserted element becomes the current one.
+    ///
+    /// # Errors
+    /// If the StackVec is full, returns back the element
+    /// inside an Err variant.
+    pub fn insert_after(&mut self, val: T) -> Result<(), T> {
+        if self.len() == CAP {
+            self.sv.overflow("insert_after");
+            return Err(val);
+        }
+        let back = self.back;
+        let base = self.base();
+        unsafe {
+            /* SAFETY: The gap between both parts has room for one more element.
+             * Move the current element one slot down, and write the new one
+             * right after it */
+            let current = base.add(CAP - back);
+            if back > 0 {
+                ptr::copy(current, current.sub(1), 1);
+                current.write(val);
+            } else {
+                current.sub(1).write(val);
+            }
+        }
+        self.back += 1;
+        Ok(())
+    }
+}
+
+impl<T, const CAP: usize> Drop for CursorMut<'_, T, CAP> {
+    fn drop(&mut self) {
+        let (front, back) = (self.front, self.back);
+        let base = self.base();
+        unsafe {
+            /* SAFETY: Move the back part right after the front part */
+            ptr::copy(base.add(CAP - back), base.add(front), back);
+        }
+        self.sv.length = front + back;
+        self.sv.poison(front + back, CAP);
+    }
+}
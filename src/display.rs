@@ -0,0 +1,25 @@
+use core::fmt;
+
+/// An adapter that displays the elements of a [StackVec],
+/// separated by a string.
+///
+/// This struct is created by [StackVec::display_separated].
+///
+/// [StackVec]: crate::StackVec
+/// [StackVec::display_separated]: crate::StackVec::display_separated
+pub struct DisplaySeparated<'a, T> {
+    pub(super) elems: &'a [T],
+    pub(super) sep: &'a str,
+}
+
+impl<T: fmt::Display> fmt::Display for DisplaySeparated<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, elem) in self.elems.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.sep)?;
+            }
+            elem.fmt(f)?;
+        }
+        Ok(())
+    }
+}
@@ -1,34 +1,124 @@
 use core::iter::FusedIterator;
 use core::marker::PhantomData;
-use core::mem;
+use core::mem::{self, ManuallyDrop};
 use core::ptr::{self, NonNull};
 use core::slice;
 
 use crate::StackVec;
 
+/// A draining iterator for [StackVec].
+///
+/// This struct is created by [StackVec::drain].
+///
+/// While the Drain is alive, the length of the StackVec only
+/// covers the elements before the drained range. The elements
+/// after it are moved back into place when the Drain is dropped.
+/// So if the Drain is leaked, those elements are leaked too, but
+/// the StackVec remains valid.
 pub struct Drain<'a, T: 'a, const CAP: usize> {
     sv: NonNull<StackVec<T, CAP>>,
     iter: slice::Iter<'a, T>,
-    start: usize,
-    len: usize,
+    tail_start: usize,
+    tail_len: usize,
     _marker: PhantomData<&'a mut StackVec<T, CAP>>,
 }
 
 impl<'a, T: 'a, const CAP: usize> Drain<'a, T, CAP> {
-    pub(super) fn new(
-        sv: NonNull<StackVec<T, CAP>>,
-        iter: slice::Iter<'a, T>,
-        start: usize,
-        len: usize,
-    ) -> Self {
+    /// Creates a Drain for the range [start, end), which must be valid
+    pub(super) fn new(sv: &'a mut StackVec<T, CAP>, start: usize, end: usize) -> Self {
+        let tail_len = sv.length - end;
+        sv.length = start;
+
+        /* SAFETY: The elements in [start, end) are initialized */
+        let iter = unsafe { slice::from_raw_parts(sv.as_ptr().add(start), end - start) }.iter();
+
         Self {
-            sv,
+            sv: NonNull::from(sv),
             iter,
-            start,
-            len,
+            tail_start: end,
+            tail_len,
             _marker: PhantomData,
         }
     }
+
+    /// Drops the first `n` elements that haven't been drained yet, at once.
+    /// If `from_back` is true, drops the last `n` elements instead.
+    fn drop_n(&mut self, n: usize, from_back: bool) {
+        let rest = self.iter.as_slice();
+        let n = n.min(rest.len());
+        let (dropped, kept) = if from_back {
+            let (kept, dropped) = rest.split_at(rest.len() - n);
+            (dropped, kept)
+        } else {
+            rest.split_at(n)
+        };
+        self.iter = kept.iter();
+
+        unsafe {
+            /* SAFETY: The dropped elements are initialized, and since we've
+             * removed them from the iterator, they won't be read again. We
+             * derive a mutable pointer from the StackVec to drop them */
+            let base = ptr::addr_of_mut!((*self.sv.as_ptr()).inner) as *mut T;
+            let offset = dropped.as_ptr().offset_from(base);
+            let dropped = ptr::slice_from_raw_parts_mut(base.offset(offset), n);
+            ptr::drop_in_place(dropped);
+        }
+    }
+
+    /// Returns the elements that haven't been drained yet, as a slice
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4]);
+    /// let mut drain = sv.drain(..);
+    ///
+    /// assert_eq!(drain.next(), Some(1));
+    /// assert_eq!(drain.as_slice(), &[2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_slice()
+    }
+
+    /// Stops draining, and keeps the elements that haven't
+    /// been drained yet in the StackVec.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4, 5]);
+    /// let mut drain = sv.drain(1..4);
+    ///
+    /// assert_eq!(drain.next(), Some(2));
+    /// drain.keep_rest();
+    ///
+    /// assert_eq!(sv.as_slice(), &[1, 3, 4, 5]);
+    /// ```
+    pub fn keep_rest(self) {
+        let mut this = ManuallyDrop::new(self);
+
+        unsafe {
+            let sv = this.sv.as_mut();
+            let start = sv.length;
+            let base = sv.as_mut_ptr();
+
+            let unconsumed = this.iter.as_slice();
+            let kept = unconsumed.len();
+
+            /* SAFETY: The unconsumed elements are still initialized,
+             * and the elements in the tail too. Moving them right after
+             * the start of the range leaves a contiguous initialized prefix */
+            let dst = base.add(start);
+            ptr::copy(unconsumed.as_ptr(), dst, kept);
+            ptr::copy(base.add(this.tail_start), dst.add(kept), this.tail_len);
+
+            sv.length = start + kept + this.tail_len;
+            sv.poison(sv.length, this.tail_start + this.tail_len);
+        }
+    }
 }
 
 impl<T, const CAP: usize> Iterator for Drain<'_, T, CAP> {
@@ -41,12 +131,33 @@ impl<T, const CAP: usize> Iterator for Drain<'_, T, CAP> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.iter.size_hint()
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.drop_n(n, false);
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        /* The rest of elements are dropped along with self */
+        self.next_back()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
 }
 
 impl<T, const CAP: usize> DoubleEndedIterator for Drain<'_, T, CAP> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back().map(|p| unsafe { ptr::read(p) })
     }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.drop_n(n, true);
+        self.next_back()
+    }
 }
 
 impl<T, const CAP: usize> FusedIterator for Drain<'_, T, CAP> {}
@@ -60,21 +171,21 @@ impl<T, const CAP: usize> ExactSizeIterator for Drain<'_, T, CAP> {
 impl<T, const CAP: usize> Drop for Drain<'_, T, CAP> {
     fn drop(&mut self) {
         if mem::needs_drop::<T>() {
-            self.for_each(drop);
+            /* Drop the elements that haven't been drained, at once */
+            self.drop_n(usize::MAX, false);
         }
 
-        if self.len > 0 {
-            unsafe {
-                let sv = self.sv.as_mut();
+        unsafe {
+            let sv = self.sv.as_mut();
+            let start = sv.length;
 
-                let dst = sv.as_mut_ptr().add(self.start);
-                let src = dst.add(self.len);
-                let n = sv.length - (self.start + self.len);
-
-                ptr::copy(src, dst, n);
-
-                sv.length -= self.len;
+            if self.tail_start != start {
+                let base = sv.as_mut_ptr();
+                ptr::copy(base.add(self.tail_start), base.add(start), self.tail_len);
             }
+
+            sv.length = start + self.tail_len;
+            sv.poison(sv.length, self.tail_start + self.tail_len);
         }
     }
 }
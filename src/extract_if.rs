@@ -0,0 +1,96 @@
+use core::iter::FusedIterator;
+use core::ptr;
+
+use crate::StackVec;
+
+/// An iterator that removes the elements of a [StackVec]
+/// that match a predicate.
+///
+/// This struct is created by [StackVec::extract_if].
+pub struct ExtractIf<'a, T, F, const CAP: usize>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    sv: &'a mut StackVec<T, CAP>,
+    /* Index of the next element to inspect */
+    idx: usize,
+    /* Number of elements removed so far */
+    del: usize,
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, T, F, const CAP: usize> ExtractIf<'a, T, F, CAP>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    pub(super) fn new(sv: &'a mut StackVec<T, CAP>, pred: F) -> Self {
+        let old_len = sv.length;
+        /* If this iterator is leaked, the StackVec will just
+         * leak its elements, instead of exposing moved out values */
+        sv.length = 0;
+        Self {
+            sv,
+            idx: 0,
+            del: 0,
+            old_len,
+            pred,
+        }
+    }
+}
+
+impl<T, F, const CAP: usize> Iterator for ExtractIf<'_, T, F, CAP>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let base = self.sv.as_mut_ptr();
+        while self.idx < self.old_len {
+            unsafe {
+                /* SAFETY: Elements in [idx, old_len) are initialized
+                 * and haven't been moved yet */
+                let cur = base.add(self.idx);
+                let matched = (self.pred)(&mut *cur);
+                self.idx += 1;
+                if matched {
+                    self.del += 1;
+                    return Some(ptr::read(cur));
+                } else if self.del > 0 {
+                    /* Compact the kept element over the removed ones */
+                    ptr::copy_nonoverlapping(cur, cur.sub(self.del), 1);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<T, F, const CAP: usize> FusedIterator for ExtractIf<'_, T, F, CAP> where
+    F: FnMut(&mut T) -> bool
+{
+}
+
+impl<T, F, const CAP: usize> Drop for ExtractIf<'_, T, F, CAP>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn drop(&mut self) {
+        unsafe {
+            /* SAFETY: Elements in [idx, old_len) haven't been inspected
+             * (or the predicate panicked on them), so they are kept */
+            let base = self.sv.as_mut_ptr();
+            if self.del > 0 {
+                let src = base.add(self.idx);
+                ptr::copy(src, src.sub(self.del), self.old_len - self.idx);
+            }
+        }
+        self.sv.length = self.old_len - self.del;
+        self.sv.poison(self.sv.length, self.old_len);
+    }
+}
@@ -0,0 +1,329 @@
+//! A text gap buffer with a fixed capacity
+//!
+//! See [StackGapBuffer]
+
+use core::fmt;
+use core::str;
+
+use crate::{CapacityError, StackString};
+
+/// A UTF-8 text buffer with a movable cursor, and a fixed capacity of CAP bytes.
+///
+/// The free space (the gap) is kept at the cursor, so inserting and
+/// deleting there doesn't shift the rest of the text. Moving the
+/// cursor only copies the bytes it moves over. This makes it suited
+/// for line editors, where most edits happen around the same spot.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackGapBuffer;
+///
+/// let mut line = StackGapBuffer::<32>::new();
+/// line.insert_str("led of").unwrap();
+///
+/// /* Fix a typo at the start */
+/// line.move_to_start();
+/// line.move_right();
+/// line.move_right();
+/// line.move_right();
+/// line.insert_str(" 1").unwrap();
+///
+/// line.move_to_end();
+/// line.delete_backward();
+/// line.insert('n').unwrap();
+///
+/// assert_eq!(line.before(), "led 1 on");
+/// assert_eq!(line.to_string(), "led 1 on");
+/// ```
+pub struct StackGapBuffer<const CAP: usize> {
+    buf: [u8; CAP],
+    /* The text is buf[..gap_start] followed by buf[gap_end..] */
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl<const CAP: usize> StackGapBuffer<CAP> {
+    /// Creates a new empty StackGapBuffer
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; CAP],
+            gap_start: 0,
+            gap_end: CAP,
+        }
+    }
+
+    /// Returns the text before the cursor
+    #[inline]
+    pub fn before(&self) -> &str {
+        /* SAFETY: The gap is always on a char boundary */
+        unsafe { str::from_utf8_unchecked(&self.buf[..self.gap_start]) }
+    }
+
+    /// Returns the text after the cursor
+    #[inline]
+    pub fn after(&self) -> &str {
+        /* SAFETY: The gap is always on a char boundary */
+        unsafe { str::from_utf8_unchecked(&self.buf[self.gap_end..]) }
+    }
+
+    /// Returns the position of the cursor, as a byte offset into the text
+    #[inline(always)]
+    pub const fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    /// Attempts to insert a char at the cursor, and places the cursor after it.
+    ///
+    /// # Errors
+    /// - If there's no room for the char, returns it back
+    ///   inside an Err variant.
+    pub fn insert(&mut self, c: char) -> Result<(), char> {
+        let mut buf = [0; 4];
+        self.insert_str(c.encode_utf8(&mut buf)).map_err(|_| c)
+    }
+
+    /// Attempts to insert a string slice at the cursor,
+    /// and places the cursor after it.
+    ///
+    /// # Errors
+    /// If there's no room for the whole string, nothing is
+    /// inserted, and a [CapacityError] is returned.
+    pub fn insert_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        let n = s.len();
+        if n > self.remaining_capacity() {
+            return Err(CapacityError);
+        }
+        self.buf[self.gap_start..self.gap_start + n].copy_from_slice(s.as_bytes());
+        self.gap_start += n;
+        Ok(())
+    }
+
+    /// Removes the char before the cursor, and returns it.
+    /// If the cursor is at the start, returns None
+    pub fn delete_backward(&mut self) -> Option<char> {
+        let c = self.before().chars().next_back()?;
+        self.gap_start -= c.len_utf8();
+        Some(c)
+    }
+
+    /// Removes the char after the cursor, and returns it.
+    /// If the cursor is at the end, returns None
+    pub fn delete_forward(&mut self) -> Option<char> {
+        let c = self.after().chars().next()?;
+        self.gap_end += c.len_utf8();
+        Some(c)
+    }
+
+    /// Moves the cursor one char to the left.
+    /// Returns false if it was already at the start.
+    pub fn move_left(&mut self) -> bool {
+        match self.before().chars().next_back() {
+            Some(c) => {
+                self.move_to(self.gap_start - c.len_utf8());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the cursor one char to the right.
+    /// Returns false if it was already at the end.
+    pub fn move_right(&mut self) -> bool {
+        match self.after().chars().next() {
+            Some(c) => {
+                self.move_to(self.gap_start + c.len_utf8());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the cursor to the start of the text
+    #[inline]
+    pub fn move_to_start(&mut self) {
+        self.move_to(0);
+    }
+
+    /// Moves the cursor to the end of the text
+    #[inline]
+    pub fn move_to_end(&mut self) {
+        self.move_to(self.len());
+    }
+
+    /// Moves the cursor to the given byte offset into the text.
+    ///
+    /// Only the bytes between the old and the new
+    /// position of the cursor are copied.
+    ///
+    /// # Panics
+    /// - If `pos` is greater than the length of the text
+    /// - If `pos` is not on a char boundary
+    pub fn move_to(&mut self, pos: usize) {
+        let gap = self.gap_end - self.gap_start;
+        if pos < self.gap_start {
+            assert!(
+                self.before().is_char_boundary(pos),
+                "Position {pos} is not on a char boundary"
+            );
+            self.buf.copy_within(pos..self.gap_start, pos + gap);
+        } else if pos > self.gap_start {
+            assert!(
+                pos <= self.len(),
+                "Position {pos} out of bounds for a StackGapBuffer of length {}",
+                self.len()
+            );
+            let n = pos - self.gap_start;
+            assert!(
+                self.after().is_char_boundary(n),
+                "Position {pos} is not on a char boundary"
+            );
+            self.buf
+                .copy_within(self.gap_end..self.gap_end + n, self.gap_start);
+        }
+        self.gap_start = pos;
+        self.gap_end = pos + gap;
+    }
+
+    /// Removes all the text
+    #[inline]
+    pub fn clear(&mut self) {
+        self.gap_start = 0;
+        self.gap_end = CAP;
+    }
+
+    /// Copies the text into a [StackString]
+    pub fn to_stack_string(&self) -> StackString<CAP> {
+        let mut s = StackString::new();
+        /* The text is at most CAP bytes long */
+        s.push_str(self.before());
+        s.push_str(self.after());
+        s
+    }
+
+    /// Returns the capacity of this StackGapBuffer, in bytes
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns how many more bytes can be inserted
+    #[inline(always)]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    /// Returns the length of the text, in bytes
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        CAP - self.remaining_capacity()
+    }
+
+    /// Returns true if the length is 0
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if no more bytes can be inserted
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.gap_start == self.gap_end
+    }
+}
+
+impl<const CAP: usize> Default for StackGapBuffer<CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> Clone for StackGapBuffer<CAP> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            buf: self.buf,
+            gap_start: self.gap_start,
+            gap_end: self.gap_end,
+        }
+    }
+}
+
+impl<const CAP: usize> From<StackString<CAP>> for StackGapBuffer<CAP> {
+    /// Creates a StackGapBuffer with the contents
+    /// of the string, and the cursor at the end
+    fn from(s: StackString<CAP>) -> Self {
+        let mut gb = Self::new();
+        let n = s.len();
+        gb.buf[..n].copy_from_slice(s.as_bytes());
+        gb.gap_start = n;
+        gb
+    }
+}
+
+impl<const CAP: usize> From<StackGapBuffer<CAP>> for StackString<CAP> {
+    #[inline]
+    fn from(gb: StackGapBuffer<CAP>) -> Self {
+        gb.to_stack_string()
+    }
+}
+
+impl<'a, const CAP: usize> TryFrom<&'a str> for StackGapBuffer<CAP> {
+    type Error = CapacityError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut gb = Self::new();
+        gb.insert_str(value)?;
+        Ok(gb)
+    }
+}
+
+impl<const CAP: usize> fmt::Write for StackGapBuffer<CAP> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.insert_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const CAP: usize> fmt::Display for StackGapBuffer<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.before())?;
+        f.write_str(self.after())
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for StackGapBuffer<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackGapBuffer")
+            .field("before", &self.before())
+            .field("after", &self.after())
+            .finish()
+    }
+}
+
+impl<const CAP: usize> PartialEq for StackGapBuffer<CAP> {
+    /// Compares the text, regardless of the cursor
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        *self == *other.to_stack_string().as_str()
+    }
+}
+
+impl<const CAP: usize> Eq for StackGapBuffer<CAP> {}
+
+impl<const CAP: usize> PartialEq<str> for StackGapBuffer<CAP> {
+    fn eq(&self, other: &str) -> bool {
+        let (head, tail) = (self.before(), self.after());
+        other.len() == head.len() + tail.len()
+            && other.as_bytes().starts_with(head.as_bytes())
+            && other.as_bytes().ends_with(tail.as_bytes())
+    }
+}
+
+impl<const CAP: usize> PartialEq<&str> for StackGapBuffer<CAP> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
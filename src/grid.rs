@@ -0,0 +1,293 @@
+//! A fixed-size two dimensional grid
+//!
+//! See [StackGrid]
+
+use core::iter::FusedIterator;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// A grid of H rows and W columns, stored inline in row-major order.
+///
+/// Cells are addressed with `(row, column)` pairs.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackGrid;
+///
+/// let mut grid = StackGrid::<i32, 3, 2>::from_fn(|row, col| (row * 3 + col) as i32);
+///
+/// assert_eq!(grid[(1, 2)], 5);
+/// grid[(0, 0)] = 10;
+///
+/// let sums = grid.columns().map(|c| c.sum()).collect::<Vec<i32>>();
+/// assert_eq!(sums, [13, 5, 7]);
+///
+/// let row_sums = grid.rows().map(|r| r.iter().sum()).collect::<Vec<i32>>();
+/// assert_eq!(row_sums, [13, 12]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StackGrid<T, const W: usize, const H: usize> {
+    cells: [[T; W]; H],
+}
+
+impl<T, const W: usize, const H: usize> StackGrid<T, W, H> {
+    /// Creates a new StackGrid from an array of rows
+    #[inline]
+    pub const fn from_rows(rows: [[T; W]; H]) -> Self {
+        Self { cells: rows }
+    }
+
+    /// Creates a new StackGrid, filled with clones of the given value
+    #[inline]
+    pub fn filled(val: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(|_, _| val.clone())
+    }
+
+    /// Creates a new StackGrid, calling the given function
+    /// with the row and column of each cell
+    #[inline]
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        Self {
+            cells: core::array::from_fn(|row| core::array::from_fn(|col| f(row, col))),
+        }
+    }
+
+    /// Consumes the grid, returning its rows
+    #[inline]
+    pub fn into_rows(self) -> [[T; W]; H] {
+        self.cells
+    }
+
+    /// Returns the number of columns
+    #[inline(always)]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// Returns the number of rows
+    #[inline(always)]
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// Returns a reference to the cell at the given row and column
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.cells.get(row)?.get(col)
+    }
+
+    /// Returns a mutable reference to the cell at the given row and column
+    #[inline]
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.cells.get_mut(row)?.get_mut(col)
+    }
+
+    /// Returns all the cells, in row-major order
+    #[inline]
+    pub const fn as_flattened(&self) -> &[T] {
+        self.cells.as_flattened()
+    }
+
+    /// Returns all the cells, in row-major order, as a mutable slice
+    #[inline]
+    pub const fn as_flattened_mut(&mut self) -> &mut [T] {
+        self.cells.as_flattened_mut()
+    }
+
+    /// Returns a reference to the given row
+    #[inline]
+    pub fn row(&self, row: usize) -> Option<&[T; W]> {
+        self.cells.get(row)
+    }
+
+    /// Returns a mutable reference to the given row
+    #[inline]
+    pub fn row_mut(&mut self, row: usize) -> Option<&mut [T; W]> {
+        self.cells.get_mut(row)
+    }
+
+    /// Returns an iterator over the rows of the grid
+    #[inline]
+    pub fn rows(&self) -> slice::Iter<'_, [T; W]> {
+        self.cells.iter()
+    }
+
+    /// Returns an iterator over mutable references to the rows of the grid
+    #[inline]
+    pub fn rows_mut(&mut self) -> slice::IterMut<'_, [T; W]> {
+        self.cells.iter_mut()
+    }
+
+    /// Returns an iterator over the cells of the given column,
+    /// from top to bottom, or None if it's out of bounds
+    #[inline]
+    pub fn column(&self, col: usize) -> Option<Column<'_, T, W>> {
+        (col < W).then(|| Column {
+            rows: self.cells.iter(),
+            col,
+        })
+    }
+
+    /// Returns an iterator over the columns of the grid, from left to right.
+    ///
+    /// Each column is itself an iterator over its cells.
+    #[inline]
+    pub fn columns(&self) -> Columns<'_, T, W> {
+        Columns {
+            rows: &self.cells,
+            cols: 0..W,
+        }
+    }
+
+    /// Returns an iterator over the cells of the grid, in row-major
+    /// order, along with their `(row, column)` position
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackGrid;
+    ///
+    /// let grid = StackGrid::from_rows([[0, 1], [1, 0]]);
+    /// let ones = grid
+    ///     .iter_indexed()
+    ///     .filter(|(_, v)| **v == 1)
+    ///     .map(|(pos, _)| pos)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(ones, [(0, 1), (1, 0)]);
+    /// ```
+    pub fn iter_indexed(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = ((usize, usize), &T)> + ExactSizeIterator {
+        self.as_flattened()
+            .iter()
+            .enumerate()
+            .map(|(i, v)| ((i / W, i % W), v))
+    }
+}
+
+impl<T, const N: usize> StackGrid<T, N, N> {
+    /// Transposes this square grid in place, swapping
+    /// the cell at `(i, j)` with the one at `(j, i)`
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackGrid;
+    ///
+    /// let mut grid = StackGrid::from_rows([[1, 2], [3, 4]]);
+    /// grid.transpose();
+    ///
+    /// assert_eq!(grid.into_rows(), [[1, 3], [2, 4]]);
+    /// ```
+    pub fn transpose(&mut self) {
+        for i in 0..N {
+            let (top, bottom) = self.cells.split_at_mut(i + 1);
+            let row = &mut top[i];
+            for (j, other) in bottom.iter_mut().enumerate() {
+                core::mem::swap(&mut row[i + 1 + j], &mut other[i]);
+            }
+        }
+    }
+}
+
+impl<T: Default, const W: usize, const H: usize> Default for StackGrid<T, W, H> {
+    #[inline]
+    fn default() -> Self {
+        Self::from_fn(|_, _| T::default())
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<(usize, usize)> for StackGrid<T, W, H> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.cells[row][col]
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<(usize, usize)> for StackGrid<T, W, H> {
+    #[inline]
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        &mut self.cells[row][col]
+    }
+}
+
+/// An iterator over the cells of a column of a [StackGrid].
+///
+/// This struct is created by [StackGrid::column] and [StackGrid::columns].
+pub struct Column<'a, T, const W: usize> {
+    rows: slice::Iter<'a, [T; W]>,
+    col: usize,
+}
+
+impl<'a, T, const W: usize> Iterator for Column<'a, T, W> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.rows.next().map(|r| &r[self.col])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<T, const W: usize> DoubleEndedIterator for Column<'_, T, W> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rows.next_back().map(|r| &r[self.col])
+    }
+}
+
+impl<T, const W: usize> ExactSizeIterator for Column<'_, T, W> {}
+
+impl<T, const W: usize> FusedIterator for Column<'_, T, W> {}
+
+/// An iterator over the columns of a [StackGrid].
+///
+/// This struct is created by [StackGrid::columns].
+pub struct Columns<'a, T, const W: usize> {
+    rows: &'a [[T; W]],
+    cols: core::ops::Range<usize>,
+}
+
+impl<'a, T, const W: usize> Iterator for Columns<'a, T, W> {
+    type Item = Column<'a, T, W>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let col = self.cols.next()?;
+        Some(Column {
+            rows: self.rows.iter(),
+            col,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cols.size_hint()
+    }
+}
+
+impl<T, const W: usize> DoubleEndedIterator for Columns<'_, T, W> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let col = self.cols.next_back()?;
+        Some(Column {
+            rows: self.rows.iter(),
+            col,
+        })
+    }
+}
+
+impl<T, const W: usize> ExactSizeIterator for Columns<'_, T, W> {}
+
+impl<T, const W: usize> FusedIterator for Columns<'_, T, W> {}
@@ -0,0 +1,465 @@
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+use core::iter::FusedIterator;
+use core::mem;
+use core::slice;
+
+/// 64-bit [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function) hasher
+///
+/// This is the default hasher for [StackHashMap]. It's
+/// very cheap for small keys, but it's not resistant to
+/// HashDoS attacks.
+pub struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    #[inline]
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// A [BuildHasher] for [FnvHasher]
+pub type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
+
+/// A hasher that uses the value of an integer key as its hash.
+///
+/// This is meant for integer keys (or keys that hash a single
+/// integer), where the keys are already well distributed. Keys
+/// that write several values (tuples, strings...) fold all of them
+/// into the hash, so they work too, but with a poorer distribution.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackHashMap;
+/// use stack_vector::hash_map::IdentityBuildHasher;
+///
+/// let mut map = StackHashMap::<u16, char, 8, IdentityBuildHasher>::default();
+/// map.insert(12, 'a');
+/// assert_eq!(map.get(&12), Some(&'a'));
+/// ```
+#[derive(Default)]
+pub struct IdentityHasher(u64);
+
+impl IdentityHasher {
+    /// Folds the value into the state. A single value
+    /// written into a new hasher is kept unchanged.
+    #[inline(always)]
+    fn fold(&mut self, i: u64) {
+        self.0 = self.0.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ i;
+    }
+}
+
+impl Hasher for IdentityHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 = self.0.rotate_left(8) ^ *b as u64;
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.fold(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.fold(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.fold(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.fold(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.fold(i as u64);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_u8(i as u8);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_usize(i as usize);
+    }
+}
+
+/// A [BuildHasher] for [IdentityHasher]
+pub type IdentityBuildHasher = BuildHasherDefault<IdentityHasher>;
+
+enum Slot<K, V> {
+    Empty,
+    Tombstone,
+    Full(K, V),
+}
+
+/// A hash map with a fixed capacity, stored inline.
+///
+/// Collisions are resolved with open addressing (linear probing)
+/// over an array of CAP slots. To keep probe sequences short, the
+/// map holds at most [max_len](Self::max_len) entries, which is
+/// 3/4 of its capacity.
+///
+/// The hashing algorithm can be customized with the S
+/// parameter. By default, [FnvHasher] is used.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackHashMap;
+///
+/// let mut map = StackHashMap::<u32, &str, 8>::new();
+///
+/// map.try_insert(1, "one").unwrap();
+/// map.try_insert(2, "two").unwrap();
+///
+/// assert_eq!(map.get(&1), Some(&"one"));
+/// assert_eq!(map.remove(&2), Some("two"));
+/// assert_eq!(map.get(&2), None);
+/// ```
+pub struct StackHashMap<K, V, const CAP: usize, S = FnvBuildHasher> {
+    slots: [Slot<K, V>; CAP],
+    length: usize,
+    tombstones: usize,
+    hash_builder: S,
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn capacity_overflow() -> ! {
+    panic!("Attempt to insert beyond the capacity of the map")
+}
+
+impl<K, V, const CAP: usize> StackHashMap<K, V, CAP> {
+    /// Creates a new empty StackHashMap
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_hasher(BuildHasherDefault::new())
+    }
+}
+
+impl<K, V, const CAP: usize, S: BuildHasher> StackHashMap<K, V, CAP, S> {
+    /// Creates a new empty StackHashMap, which will use
+    /// the given [BuildHasher] to hash the keys.
+    #[inline]
+    pub const fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            slots: [const { Slot::Empty }; CAP],
+            length: 0,
+            tombstones: 0,
+            hash_builder,
+        }
+    }
+
+    /// Returns a reference to the map's [BuildHasher]
+    #[inline(always)]
+    pub const fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    fn start_index<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        (self.hash_builder.hash_one(key) % CAP as u64) as usize
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if CAP == 0 {
+            return None;
+        }
+        let start = self.start_index(key);
+        for i in 0..CAP {
+            let idx = (start + i) % CAP;
+            match &self.slots[idx] {
+                Slot::Empty => return None,
+                Slot::Full(k, _) if k.borrow() == key => return Some(idx),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Rebuilds the table in place, getting rid of the tombstones
+    fn rehash(&mut self)
+    where
+        K: Hash,
+    {
+        let old = mem::replace(&mut self.slots, [const { Slot::Empty }; CAP]);
+        self.tombstones = 0;
+        for slot in old {
+            if let Slot::Full(k, v) = slot {
+                let idx = self.free_slot(&k);
+                self.slots[idx] = Slot::Full(k, v);
+            }
+        }
+    }
+
+    /// Returns the first non full slot in the probe sequence of the key.
+    /// The caller must make sure that there's at least one.
+    fn free_slot(&self, key: &K) -> usize
+    where
+        K: Hash,
+    {
+        let start = self.start_index(key);
+        (0..CAP)
+            .map(|i| (start + i) % CAP)
+            .find(|&i| !matches!(self.slots[i], Slot::Full(..)))
+            .unwrap_or_else(|| unreachable!())
+    }
+
+    /// Attempts to insert a key-value pair into the map.
+    ///
+    /// If the key was already present, its value is replaced,
+    /// and the old value is returned.
+    ///
+    /// # Errors
+    /// If the key is not present, and inserting it would exceed
+    /// the [max_len](Self::max_len) of the map, returns back the
+    /// key-value pair inside an Err variant.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)>
+    where
+        K: Hash + Eq,
+    {
+        if let Some(i) = self.find(&key) {
+            let Slot::Full(_, v) = &mut self.slots[i] else {
+                unreachable!()
+            };
+            return Ok(Some(mem::replace(v, value)));
+        }
+
+        if self.length >= Self::max_len() {
+            self.overflow("try_insert");
+            return Err((key, value));
+        }
+        if self.length + self.tombstones >= Self::max_len() {
+            self.rehash();
+        }
+
+        let idx = self.free_slot(&key);
+        if matches!(self.slots[idx], Slot::Tombstone) {
+            self.tombstones -= 1;
+        }
+        self.slots[idx] = Slot::Full(key, value);
+        self.length += 1;
+        Ok(None)
+    }
+
+    /// Inserts a key-value pair into the map, panicking if there is no space left.
+    ///
+    /// # Panics
+    /// - If the map is full
+    #[inline]
+    #[track_caller]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: Hash + Eq,
+    {
+        match self.try_insert(key, value) {
+            Ok(old) => old,
+            Err(_) => capacity_overflow(),
+        }
+    }
+
+    /// Returns a reference to the value associated with the given key
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match &self.slots[self.find(key)?] {
+            Slot::Full(_, v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value associated with the given key
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.find(key)?;
+        match &mut self.slots[i] {
+            Slot::Full(_, v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the map contains the given key
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Removes the given key from the map, returning its value
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.find(key)?;
+        match mem::replace(&mut self.slots[i], Slot::Tombstone) {
+            Slot::Full(_, v) => {
+                self.length -= 1;
+                self.tombstones += 1;
+                Some(v)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns an iterator over the key-value pairs of the map
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            left: self.length,
+        }
+    }
+
+    /// Removes all the entries from the map
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|s| *s = Slot::Empty);
+        self.length = 0;
+        self.tombstones = 0;
+    }
+
+    /// Calls the overflow hook, if the `overflow-hook` feature is enabled.
+    /// The capacity reported is [max_len](Self::max_len).
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn overflow(&self, operation: &'static str) {
+        #[cfg(feature = "overflow-hook")]
+        crate::hook::report::<(K, V)>(Self::max_len(), self.length, operation);
+    }
+
+    /// Returns the maximum number of entries this map can hold.
+    ///
+    /// This is 3/4 of the capacity, rounded up.
+    #[inline(always)]
+    pub const fn max_len() -> usize {
+        CAP - CAP / 4
+    }
+
+    /// Returns the capacity (number of slots) of this StackHashMap
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of entries in the map
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns true if the map has no entries
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns true if no more entries can be inserted into the map
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.length >= Self::max_len()
+    }
+}
+
+impl<K, V, const CAP: usize, S: Default + BuildHasher> Default for StackHashMap<K, V, CAP, S> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<'a, K, V, const CAP: usize, S> IntoIterator for &'a StackHashMap<K, V, CAP, S>
+where
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [StackHashMap]
+pub struct Iter<'a, K, V> {
+    slots: slice::Iter<'a, Slot<K, V>>,
+    left: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, v) = self.slots.by_ref().find_map(|s| match s {
+            Slot::Full(k, v) => Some((k, v)),
+            _ => None,
+        })?;
+        self.left -= 1;
+        Some((k, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
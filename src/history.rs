@@ -0,0 +1,218 @@
+use core::iter::{Chain, FusedIterator};
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+use core::slice;
+
+/// A ring buffer that keeps the last CAP elements written into it.
+///
+/// When full, writing a new element overwrites the oldest one.
+///
+/// # Example
+/// ```
+/// use stack_vector::HistoryBuffer;
+///
+/// let mut samples = HistoryBuffer::<u32, 3>::new();
+///
+/// for s in 1..=5 {
+///     samples.write(s);
+/// }
+///
+/// assert_eq!(samples.recent(), Some(&5));
+/// assert_eq!(samples.oldest(), Some(&3));
+/// assert!(samples.iter().eq(&[3, 4, 5]));
+/// ```
+pub struct HistoryBuffer<T, const CAP: usize> {
+    inner: [MaybeUninit<T>; CAP],
+    /* Index of the next slot to write */
+    write_at: usize,
+    length: usize,
+}
+
+impl<T, const CAP: usize> HistoryBuffer<T, CAP> {
+    /// Creates a new empty HistoryBuffer
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            inner: [const { MaybeUninit::uninit() }; CAP],
+            write_at: 0,
+            length: 0,
+        }
+    }
+
+    /// Writes an element into the buffer.
+    ///
+    /// If the buffer was full, the oldest element is
+    /// overwritten, and returned. If CAP is 0, the
+    /// element itself is returned.
+    pub fn write(&mut self, val: T) -> Option<T> {
+        if CAP == 0 {
+            return Some(val);
+        }
+        let slot = &mut self.inner[self.write_at];
+        let old = if self.length == CAP {
+            /* SAFETY: The buffer is full, so every slot is initialized */
+            Some(unsafe { mem::replace(slot, MaybeUninit::new(val)).assume_init() })
+        } else {
+            slot.write(val);
+            self.length += 1;
+            None
+        };
+        self.write_at = (self.write_at + 1) % CAP;
+        old
+    }
+
+    /// Writes all the elements of the iterator into the buffer
+    #[inline]
+    pub fn extend_from_iter<I>(&mut self, it: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in it {
+            self.write(elem);
+        }
+    }
+
+    /// Returns the two contiguous slices that form the buffer,
+    /// in order from the oldest to the most recent element.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let start = self.oldest_index();
+        let first_len = self.length.min(CAP - start);
+        let ptr = self.inner.as_ptr() as *const T;
+        unsafe {
+            /* SAFETY: The elements in [start, start + first_len)
+             * and [0, length - first_len) are initialized */
+            (
+                slice::from_raw_parts(ptr.add(start), first_len),
+                slice::from_raw_parts(ptr, self.length - first_len),
+            )
+        }
+    }
+
+    #[inline]
+    fn oldest_index(&self) -> usize {
+        if self.length == CAP { self.write_at } else { 0 }
+    }
+
+    /// Returns the most recently written element
+    pub fn recent(&self) -> Option<&T> {
+        if self.length == 0 {
+            return None;
+        }
+        let i = (self.write_at + CAP - 1) % CAP;
+        /* SAFETY: The last written slot is always initialized */
+        Some(unsafe { self.inner[i].assume_init_ref() })
+    }
+
+    /// Returns the oldest element of the buffer
+    pub fn oldest(&self) -> Option<&T> {
+        if self.length == 0 {
+            return None;
+        }
+        /* SAFETY: The buffer is not empty, so the oldest slot is initialized */
+        Some(unsafe { self.inner[self.oldest_index()].assume_init_ref() })
+    }
+
+    /// Returns an iterator over the elements, from the oldest to the most recent
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (a, b) = self.as_slices();
+        Iter {
+            iter: a.iter().chain(b.iter()),
+        }
+    }
+
+    /// Removes all the elements of the buffer
+    pub fn clear(&mut self) {
+        let start = self.oldest_index();
+        let first_len = self.length.min(CAP - start);
+        let second_len = self.length - first_len;
+        let ptr = self.inner.as_mut_ptr() as *mut T;
+        self.length = 0;
+        self.write_at = 0;
+        unsafe {
+            /* SAFETY: Both regions are initialized, and we've reset
+             * the length before, in case a Drop call fails */
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr.add(start), first_len));
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, second_len));
+        }
+    }
+
+    /// Returns the capacity of this HistoryBuffer
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of elements in the buffer
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns true if the buffer has no elements
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns true if writing a new element will overwrite the oldest one
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.length == CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for HistoryBuffer<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Drop for HistoryBuffer<T, CAP> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            self.clear();
+        }
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a HistoryBuffer<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of a [HistoryBuffer]
+pub struct Iter<'a, T> {
+    iter: Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
@@ -0,0 +1,79 @@
+//! Instrumentation of capacity overflows
+//!
+//! A global hook can be registered with [set_overflow_hook]. It will
+//! be called every time a fallible operation on a [StackVec] fails
+//! because there's not enough capacity left.
+//!
+//! # Example
+//! ```
+//! use core::sync::atomic::{AtomicUsize, Ordering};
+//! use stack_vector::StackVec;
+//! use stack_vector::hook::{self, OverflowEvent};
+//!
+//! static OVERFLOWS: AtomicUsize = AtomicUsize::new(0);
+//!
+//! fn count(_: &OverflowEvent) {
+//!     OVERFLOWS.fetch_add(1, Ordering::Relaxed);
+//! }
+//!
+//! hook::set_overflow_hook(count);
+//!
+//! let mut sv = StackVec::<u8, 1>::new();
+//! sv.try_push(1).unwrap();
+//! assert!(sv.try_push(2).is_err());
+//!
+//! assert_eq!(OVERFLOWS.load(Ordering::Relaxed), 1);
+//! # hook::take_overflow_hook();
+//! ```
+//!
+//! [StackVec]: crate::StackVec
+
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// Information about a capacity overflow
+#[derive(Debug, Clone, Copy)]
+pub struct OverflowEvent {
+    /// Name of the element type of the container
+    pub type_name: &'static str,
+    /// Capacity of the container
+    pub capacity: usize,
+    /// Length of the container when the overflow happened
+    pub len: usize,
+    /// Name of the operation that failed
+    pub operation: &'static str,
+}
+
+/// A function to be called on capacity overflows
+pub type OverflowHook = fn(&OverflowEvent);
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers the global overflow hook, replacing the previous one
+#[inline]
+pub fn set_overflow_hook(hook: OverflowHook) {
+    HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Unregisters the global overflow hook, and returns it
+pub fn take_overflow_hook() -> Option<OverflowHook> {
+    let hook = HOOK.swap(ptr::null_mut(), Ordering::AcqRel);
+    /* SAFETY: Non null values in HOOK always come from an OverflowHook */
+    (!hook.is_null()).then(|| unsafe { core::mem::transmute::<*mut (), OverflowHook>(hook) })
+}
+
+#[cold]
+pub(crate) fn report<T>(capacity: usize, len: usize, operation: &'static str) {
+    let hook = HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        return;
+    }
+    /* SAFETY: Non null values in HOOK always come from an OverflowHook */
+    let hook = unsafe { core::mem::transmute::<*mut (), OverflowHook>(hook) };
+    hook(&OverflowEvent {
+        type_name: core::any::type_name::<T>(),
+        capacity,
+        len,
+        operation,
+    });
+}
@@ -0,0 +1,154 @@
+use core::ops::{Index, IndexMut};
+
+use crate::StackVec;
+
+/// An index that is guaranteed to be less than CAP.
+///
+/// Since the check is done when the index is built, indexing a `[T; CAP]`
+/// array with it needs no bounds check at all. To use it in a hot loop
+/// over a full StackVec, get its elements as an array once, with
+/// [as_array](StackVec::as_array), and index that.
+///
+/// A StackVec that isn't full can be accessed with
+/// [get_bounded](StackVec::get_bounded), which still has to compare
+/// the index against the length.
+///
+/// # Example
+/// ```
+/// use stack_vector::{Idx, StackVec};
+///
+/// let sv = StackVec::from_array([10, 20, 30, 40]);
+///
+/// let i = Idx::<4>::new(2).unwrap();
+/// assert_eq!(sv.get_bounded(i), Some(&30));
+///
+/// let arr = sv.as_array().unwrap();
+/// let sum: i32 = Idx::all().map(|i| arr[i]).sum();
+/// assert_eq!(sum, 100);
+///
+/// assert!(Idx::<4>::new(4).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Idx<const CAP: usize>(usize);
+
+impl<const CAP: usize> Idx<CAP> {
+    /// Creates a new Idx, if `i` is less than CAP
+    #[inline]
+    pub const fn new(i: usize) -> Option<Self> {
+        if i < CAP { Some(Self(i)) } else { None }
+    }
+
+    /// Creates a new Idx, wrapping `i` around CAP
+    ///
+    /// # Panics
+    /// - If CAP is 0
+    #[inline]
+    pub const fn new_wrapping(i: usize) -> Self {
+        Self(i % CAP)
+    }
+
+    /// Creates a new Idx without checking that it's less than CAP
+    ///
+    /// # Safety
+    /// `i` must be less than CAP
+    #[inline(always)]
+    pub const unsafe fn new_unchecked(i: usize) -> Self {
+        Self(i)
+    }
+
+    /// Returns the index as a usize
+    #[inline(always)]
+    pub const fn get(self) -> usize {
+        self.0
+    }
+
+    /// Returns an iterator over all the indices in [0, CAP)
+    #[inline]
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator {
+        (0..CAP).map(Self)
+    }
+}
+
+impl<const CAP: usize> From<Idx<CAP>> for usize {
+    #[inline(always)]
+    fn from(value: Idx<CAP>) -> Self {
+        value.0
+    }
+}
+
+impl<T, const CAP: usize> Index<Idx<CAP>> for [T; CAP] {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, i: Idx<CAP>) -> &T {
+        /* SAFETY: i is less than CAP */
+        unsafe { self.get_unchecked(i.0) }
+    }
+}
+
+impl<T, const CAP: usize> IndexMut<Idx<CAP>> for [T; CAP] {
+    #[inline(always)]
+    fn index_mut(&mut self, i: Idx<CAP>) -> &mut T {
+        /* SAFETY: i is less than CAP */
+        unsafe { self.get_unchecked_mut(i.0) }
+    }
+}
+
+impl<T, const CAP: usize> StackVec<T, CAP> {
+    /// Returns the elements as an array, if the StackVec is full.
+    ///
+    /// The array can be indexed with an [Idx] without any checks.
+    #[inline]
+    pub const fn as_array(&self) -> Option<&[T; CAP]> {
+        if self.length == CAP {
+            /* SAFETY: All the elements are initialized */
+            Some(unsafe { &*(self.as_ptr() as *const [T; CAP]) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the elements as a mutable array, if the StackVec is full.
+    ///
+    /// The array can be indexed with an [Idx] without any checks.
+    #[inline]
+    pub const fn as_array_mut(&mut self) -> Option<&mut [T; CAP]> {
+        if self.length == CAP {
+            /* SAFETY: All the elements are initialized */
+            Some(unsafe { &mut *(self.as_mut_ptr() as *mut [T; CAP]) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the element at the given index,
+    /// or None if the index is not less than the length.
+    #[inline]
+    pub const fn get_bounded(&self, i: Idx<CAP>) -> Option<&T> {
+        if i.0 < self.length {
+            /* SAFETY: i is less than CAP, and the element is initialized */
+            Some(unsafe { &*self.as_ptr().add(i.0) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the element at the given
+    /// index, or None if the index is not less than the length.
+    #[inline]
+    pub const fn get_bounded_mut(&mut self, i: Idx<CAP>) -> Option<&mut T> {
+        if i.0 < self.length {
+            /* SAFETY: i is less than CAP, and the element is initialized */
+            Some(unsafe { &mut *self.as_mut_ptr().add(i.0) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the indices of the
+    /// elements of this StackVec, as [Idx]s
+    #[inline]
+    pub fn indices(&self) -> impl DoubleEndedIterator<Item = Idx<CAP>> + ExactSizeIterator {
+        (0..self.length).map(Idx)
+    }
+}
@@ -0,0 +1,160 @@
+use core::iter::FusedIterator;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+use core::slice;
+
+use crate::StackVec;
+
+/// An iterator that moves out of a [StackVec].
+///
+/// This struct is created by the [IntoIterator] implementation of [StackVec].
+pub struct IntoIter<T, const CAP: usize> {
+    inner: [MaybeUninit<T>; CAP],
+    /* Elements in [start, end) haven't been yielded yet */
+    start: usize,
+    end: usize,
+}
+
+impl<T, const CAP: usize> IntoIter<T, CAP> {
+    pub(super) fn new(sv: StackVec<T, CAP>) -> Self {
+        let sv = ManuallyDrop::new(sv);
+        let end = sv.length;
+        /* SAFETY: sv is not dropped, so its elements are moved into self */
+        let inner = unsafe { ptr::read(&sv.inner) };
+        Self {
+            inner,
+            start: 0,
+            end,
+        }
+    }
+
+    /// Returns the remaining elements of this iterator as a slice
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let sv = StackVec::from_array([1, 2, 3]);
+    /// let mut it = sv.into_iter();
+    /// it.next();
+    ///
+    /// assert_eq!(it.as_slice(), &[2, 3]);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        /* SAFETY: Elements in [start, end) are initialized */
+        unsafe { slice::from_raw_parts(self.ptr().add(self.start), self.len()) }
+    }
+
+    /// Returns the remaining elements of this iterator as a mutable slice
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        /* SAFETY: Elements in [start, end) are initialized */
+        unsafe { slice::from_raw_parts_mut(self.mut_ptr().add(self.start), len) }
+    }
+
+    #[inline(always)]
+    fn ptr(&self) -> *const T {
+        self.inner.as_ptr() as *const T
+    }
+
+    #[inline(always)]
+    fn mut_ptr(&mut self) -> *mut T {
+        self.inner.as_mut_ptr() as *mut T
+    }
+
+    /// Drops the elements in [from, to), which must have
+    /// already been removed from the [start, end) range
+    unsafe fn drop_range(&mut self, from: usize, to: usize) {
+        unsafe {
+            let range = ptr::slice_from_raw_parts_mut(self.mut_ptr().add(from), to - from);
+            ptr::drop_in_place(range);
+        }
+    }
+}
+
+impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        /* SAFETY: The element was initialized, and it's
+         * no longer in the [start, end) range */
+        Some(unsafe { self.inner[self.start - 1].assume_init_read() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.min(self.len());
+        let from = self.start;
+        self.start += skip;
+        /* SAFETY: The skipped elements are initialized */
+        unsafe { self.drop_range(from, self.start) };
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        /* SAFETY: The element was initialized, and it's
+         * no longer in the [start, end) range */
+        Some(unsafe { self.inner[self.end].assume_init_read() })
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.min(self.len());
+        let to = self.end;
+        self.end -= skip;
+        /* SAFETY: The skipped elements are initialized */
+        unsafe { self.drop_range(self.end, to) };
+        self.next_back()
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<T, const CAP: usize> FusedIterator for IntoIter<T, CAP> {}
+
+impl<T: Clone, const CAP: usize> Clone for IntoIter<T, CAP> {
+    fn clone(&self) -> Self {
+        let mut sv = StackVec::new();
+        /* There are at most CAP elements left */
+        let _ = sv.extend_cloned(self.as_slice());
+        sv.into_iter()
+    }
+}
+
+impl<T, const CAP: usize> Drop for IntoIter<T, CAP> {
+    fn drop(&mut self) {
+        let (from, to) = (self.start, self.end);
+        self.start = to;
+        /* SAFETY: The remaining elements are initialized */
+        unsafe { self.drop_range(from, to) };
+    }
+}
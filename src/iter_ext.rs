@@ -0,0 +1,103 @@
+use core::iter::{Fuse, FusedIterator};
+
+use crate::{CapacityError, StackVec};
+
+/// Extension methods for iterators, to work with [StackVec]s
+///
+/// # Example
+/// ```
+/// use stack_vector::IteratorExt;
+///
+/// let squares = (1..=4).map(|n| n * n).collect_stackvec::<4>();
+/// assert_eq!(squares.as_slice(), &[1, 4, 9, 16]);
+///
+/// assert!((0..10).try_collect_stackvec::<4>().is_err());
+/// ```
+pub trait IteratorExt: Iterator {
+    /// Collects the elements of this iterator into a StackVec
+    ///
+    /// # Panics
+    /// - If the iterator yields more than CAP elements
+    #[inline]
+    fn collect_stackvec<const CAP: usize>(self) -> StackVec<Self::Item, CAP>
+    where
+        Self: Sized,
+    {
+        let mut sv = StackVec::new();
+        sv.extend_from_iter(self);
+        sv
+    }
+
+    /// Attempts to collect the elements of this iterator into a StackVec
+    ///
+    /// # Errors
+    /// If the iterator yields more than CAP elements
+    fn try_collect_stackvec<const CAP: usize>(
+        self,
+    ) -> Result<StackVec<Self::Item, CAP>, CapacityError>
+    where
+        Self: Sized,
+    {
+        let mut sv = StackVec::new();
+        match sv.try_extend_from_iter(self) {
+            Ok(()) => Ok(sv),
+            Err(_) => Err(CapacityError),
+        }
+    }
+
+    /// Groups the elements of this iterator into StackVecs of N elements.
+    /// The last StackVec may have less than N elements.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::IteratorExt;
+    ///
+    /// let mut chunks = (1..=5).stack_chunks::<2>();
+    ///
+    /// assert_eq!(chunks.next().unwrap().as_slice(), &[1, 2]);
+    /// assert_eq!(chunks.next().unwrap().as_slice(), &[3, 4]);
+    /// assert_eq!(chunks.next().unwrap().as_slice(), &[5]);
+    /// assert!(chunks.next().is_none());
+    /// ```
+    ///
+    /// This method only compiles when N is greater than 0
+    ///
+    /// ```compile_fail
+    /// use stack_vector::IteratorExt;
+    ///
+    /// let chunks = (1..=5).stack_chunks::<0>();
+    /// ```
+    #[inline]
+    fn stack_chunks<const N: usize>(self) -> StackChunks<Self, N>
+    where
+        Self: Sized,
+    {
+        const { assert!(N > 0, "Chunk size must be greater than 0") };
+        StackChunks { iter: self.fuse() }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+/// An iterator over chunks of N elements of another iterator.
+///
+/// This struct is created by [IteratorExt::stack_chunks].
+pub struct StackChunks<I: Iterator, const N: usize> {
+    iter: Fuse<I>,
+}
+
+impl<I: Iterator, const N: usize> Iterator for StackChunks<I, N> {
+    type Item = StackVec<I::Item, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.iter.by_ref().take(N).collect_stackvec::<N>();
+        (!chunk.is_empty()).then_some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        (lo.div_ceil(N), hi.map(|hi| hi.div_ceil(N)))
+    }
+}
+
+impl<I: Iterator, const N: usize> FusedIterator for StackChunks<I, N> {}
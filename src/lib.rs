@@ -22,15 +22,184 @@
 //! ```
 
 #![no_std]
+#![cfg_attr(feature = "may-dangle", feature(dropck_eyepatch))]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
 
 use core::iter::Peekable;
+use core::marker::PhantomData;
 use core::mem::{self, ManuallyDrop, MaybeUninit};
-use core::ops::{Deref, DerefMut, RangeBounds};
+use core::ops::{AddAssign, Deref, DerefMut, Range, RangeBounds};
 use core::ptr::{self, NonNull};
+use core::slice;
 
-use drain::Drain;
+mod bitwise;
+
+mod builder;
+pub use builder::StackVecBuilder;
+
+mod cursor;
+pub use cursor::CursorMut;
+
+mod cow;
+pub use cow::StackCow;
+
+mod display;
+pub use display::DisplaySeparated;
 
 mod drain;
+pub use drain::Drain;
+
+mod extract_if;
+pub use extract_if::ExtractIf;
+
+mod idx;
+pub use idx::Idx;
+
+mod into_iter;
+pub use into_iter::IntoIter;
+
+mod iter_ext;
+pub use iter_ext::{IteratorExt, StackChunks};
+
+mod non_empty;
+pub use non_empty::NonEmptyStackVec;
+
+mod pin;
+
+#[cfg(feature = "portable-simd")]
+mod simd;
+
+mod soa;
+
+mod pop_while;
+pub use pop_while::PopWhile;
+
+mod sorted;
+
+mod split_on;
+pub use split_on::SplitOn;
+
+pub mod arena;
+pub use arena::StackArena;
+
+pub mod atomic_vec;
+pub use atomic_vec::AtomicStackVec;
+
+pub mod binary_heap;
+pub use binary_heap::StackBinaryHeap;
+
+pub mod bitset;
+pub use bitset::StackBitSet;
+
+pub mod bump;
+pub use bump::StackBumpAlloc;
+
+pub mod bytes;
+
+pub mod gap_buffer;
+pub use gap_buffer::StackGapBuffer;
+
+pub mod grid;
+pub use grid::StackGrid;
+
+pub mod hash_map;
+pub use hash_map::StackHashMap;
+
+#[cfg(feature = "overflow-hook")]
+pub mod hook;
+
+pub mod history;
+pub use history::HistoryBuffer;
+
+pub mod linked_list;
+pub use linked_list::StackLinkedList;
+
+pub mod lru;
+pub use lru::StackLruCache;
+
+pub mod map;
+pub use map::StackMap;
+
+pub mod mpmc;
+
+pub mod packet;
+pub use packet::StackPacketBuf;
+
+pub mod pool;
+pub use pool::StackPool;
+
+pub mod set;
+pub use set::StackSet;
+
+pub mod slot_map;
+pub use slot_map::StackSlotMap;
+
+pub mod spsc;
+
+pub mod string;
+pub use string::StackString;
+
+pub mod wstring;
+pub use wstring::StackWString;
+
+mod writer;
+pub use writer::TruncatingWriter;
+
+/// Byte pattern written over the slots vacated by a [StackVec],
+/// when the `poison` feature is enabled in a debug build.
+#[cfg(feature = "poison")]
+pub const POISON_BYTE: u8 = 0xA5;
+
+/// Error returned when an operation would exceed
+/// the capacity of a fixed-size container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl core::fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl core::error::Error for CapacityError {}
+
+/* The panicking paths are kept out of line, so that the
+ * formatting machinery doesn't bloat the inlined callers */
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn capacity_overflow() -> ! {
+    panic!("Attemp to push beyond the capacity of the array")
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn index_out_of_bounds(index: usize, len: usize) -> ! {
+    panic!("Index {index} out of bounds for a StackVec of length {len}")
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn invalid_range(len: usize) -> ! {
+    panic!("Invalid range for a StackVec of length {len}")
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn length_mismatch(left: usize, right: usize) -> ! {
+    panic!("Length mismatch between StackVecs ({left} != {right})")
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn length_exceeds_capacity(len: usize, cap: usize) -> ! {
+    panic!("Length {len} exceeds the capacity of the StackVec ({cap})")
+}
 
 /// A [Vec]-like wrapper for an array.
 ///
@@ -39,6 +208,10 @@ mod drain;
 pub struct StackVec<T, const CAP: usize> {
     inner: [MaybeUninit<T>; CAP],
     length: usize,
+    #[cfg(feature = "high-water-mark")]
+    high_water: usize,
+    /* Tells the drop checker that we own values of type T */
+    _marker: PhantomData<T>,
 }
 
 impl<T, const CAP: usize> StackVec<T, CAP> {
@@ -48,6 +221,9 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
         Self {
             inner: [const { MaybeUninit::uninit() }; CAP],
             length: 0,
+            #[cfg(feature = "high-water-mark")]
+            high_water: 0,
+            _marker: PhantomData,
         }
     }
 
@@ -68,6 +244,49 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
         Self::generate(|| val.clone())
     }
 
+    /// Creates a new StackVec with `n` copies of the given value.
+    ///
+    /// Unlike [filled](Self::filled), the rest of the
+    /// capacity is left free.
+    ///
+    /// # Panics
+    /// - If `n` is greater than CAP
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut line = StackVec::<char, 8>::from_element_n(' ', 3);
+    /// line.push('x');
+    ///
+    /// assert_eq!(line.as_slice(), &[' ', ' ', ' ', 'x']);
+    /// ```
+    #[track_caller]
+    pub fn from_element_n(val: T, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        if n > CAP {
+            length_exceeds_capacity(n, CAP);
+        }
+        let mut s = Self::new();
+        if n == 0 {
+            return s;
+        }
+        if bitwise::is_bitwise_clone::<T>() {
+            /* SAFETY: n is less or equal than CAP */
+            unsafe { s.fill_bitwise(val, n) };
+            return s;
+        }
+        for _ in 1..n {
+            /* SAFETY: n is less or equal than CAP */
+            unsafe { s.push_unchecked(val.clone()) };
+        }
+        /* SAFETY: There's room for the last element */
+        unsafe { s.push_unchecked(val) };
+        s
+    }
+
     /// Creates a new StackVec, filling it using the given generator function
     ///
     /// # Example
@@ -98,6 +317,133 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
         s
     }
 
+    /// Creates a new StackVec with `n` elements, generated by calling
+    /// the given function with the index of each one.
+    ///
+    /// Unlike [generate](Self::generate), the rest of the
+    /// capacity is left free.
+    ///
+    /// # Panics
+    /// - If `n` is greater than CAP
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let squares = StackVec::<usize, 8>::from_fn_n(4, |i| i * i);
+    ///
+    /// assert_eq!(squares.as_slice(), &[0, 1, 4, 9]);
+    /// assert_eq!(squares.remaining_capacity(), 4);
+    /// ```
+    #[track_caller]
+    pub fn from_fn_n<F>(n: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        if n > CAP {
+            length_exceeds_capacity(n, CAP);
+        }
+        let mut s = Self::new();
+        for i in 0..n {
+            /* SAFETY: n is less or equal than CAP */
+            unsafe { s.push_unchecked(f(i)) };
+        }
+        s
+    }
+
+    /// Creates a new full StackVec, with all its bytes set to zero.
+    ///
+    /// The buffer is zeroed at once, which is faster than pushing
+    /// CAP zeroes one by one, specially for big byte buffers.
+    ///
+    /// # Safety
+    /// A value of T with all its bytes set to zero must be valid.
+    /// This is the case for integers and floats, but not for
+    /// references, [NonNull], or most enums.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// /* SAFETY: u8 can be zero */
+    /// let buf = unsafe { StackVec::<u8, 4096>::zeroed() };
+    ///
+    /// assert!(buf.is_full());
+    /// assert!(buf.iter().all(|&b| b == 0));
+    /// ```
+    #[inline]
+    pub const unsafe fn zeroed() -> Self {
+        /* SAFETY: The caller guarantees that this is valid */
+        unsafe { Self::with_len_zeroed(CAP) }
+    }
+
+    /// Creates a new StackVec with `n` elements, all their bytes set to zero.
+    ///
+    /// # Safety
+    /// Same as [zeroed](Self::zeroed)
+    ///
+    /// # Panics
+    /// - If `n` is greater than CAP
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// /* SAFETY: u32 can be zero */
+    /// let mut counts = unsafe { StackVec::<u32, 16>::with_len_zeroed(10) };
+    /// counts[3] += 1;
+    ///
+    /// assert_eq!(counts.len(), 10);
+    /// assert_eq!(counts.iter().sum::<u32>(), 1);
+    /// ```
+    pub const unsafe fn with_len_zeroed(n: usize) -> Self {
+        assert!(n <= CAP, "Length exceeds the capacity of the StackVec");
+        let mut s = Self::new();
+        unsafe {
+            /* SAFETY: n is less or equal than CAP, and the caller
+             * guarantees that zero is a valid T */
+            ptr::write_bytes(s.as_mut_ptr(), 0, n);
+        }
+        s.length = n;
+        #[cfg(feature = "high-water-mark")]
+        {
+            s.high_water = n;
+        }
+        s
+    }
+
+    /// Creates a new StackVec, filled with as many whole
+    /// repetitions of the given pattern as fit in it.
+    ///
+    /// # Errors
+    /// If the pattern doesn't fit even once
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let v = StackVec::<u8, 8>::repeat_slice(&[0xDE, 0xAD, 0xBE]).unwrap();
+    /// assert_eq!(v.as_slice(), &[0xDE, 0xAD, 0xBE, 0xDE, 0xAD, 0xBE]);
+    ///
+    /// assert!(StackVec::<u8, 2>::repeat_slice(&[1, 2, 3]).is_err());
+    /// ```
+    pub fn repeat_slice(pattern: &[T]) -> Result<Self, CapacityError>
+    where
+        T: Clone,
+    {
+        let mut s = Self::new();
+        if pattern.is_empty() {
+            return Ok(s);
+        }
+        let times = CAP / pattern.len();
+        if times == 0 {
+            s.overflow("repeat_slice");
+            return Err(CapacityError);
+        }
+        s.extend_repeating(pattern, times)?;
+        Ok(s)
+    }
+
     /// Creates a new StackVec from the given array of T
     ///
     /// # Example
@@ -118,7 +464,13 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
             /* SAFETY: T and ManualyDrop<T> have the same size and alignment */
             mem::transmute_copy(&arr)
         };
-        Self { inner, length: CAP }
+        Self {
+            inner,
+            length: CAP,
+            #[cfg(feature = "high-water-mark")]
+            high_water: CAP,
+            _marker: PhantomData,
+        }
     }
 
     /// Pushes an element in the StackVec without checking bounds.
@@ -130,7 +482,17 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
         unsafe {
             self.as_mut_ptr().add(self.length).write(val);
         }
-        self.length += 1;
+        self.grow(1);
+    }
+
+    /// Adds `n` to the length, after the elements have been initialized
+    #[inline(always)]
+    fn grow(&mut self, n: usize) {
+        self.length += n;
+        #[cfg(feature = "high-water-mark")]
+        if self.length > self.high_water {
+            self.high_water = self.length;
+        }
     }
 
     /// Pushes an element into this StackVec, panicking if there is no space left.
@@ -138,9 +500,10 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
     /// # Panics
     /// - If the StackVec is full
     #[inline]
+    #[track_caller]
     pub fn push(&mut self, val: T) {
         if self.try_push(val).is_err() {
-            panic!("Attemp to push beyond the capacity of the array")
+            capacity_overflow()
         }
     }
 
@@ -151,6 +514,7 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
     ///   inside an Err variant.
     pub fn try_push(&mut self, val: T) -> Result<(), T> {
         if self.length >= CAP {
+            self.overflow("try_push");
             Err(val)
         } else {
             /* SAFETY: We've just checked that the buffer can
@@ -160,76 +524,546 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
         }
     }
 
-    /// Pushes all the elements from the iterator into this StackVec.
-    #[inline]
-    pub fn extend_from_iter<I>(&mut self, it: I)
+    /// Pushes an element, constructing it in place.
+    ///
+    /// The function receives the next free slot of the StackVec, and must
+    /// initialize it, returning the reference given by [MaybeUninit::write].
+    /// This avoids building a big element somewhere else, and then moving it.
+    ///
+    /// Returns a mutable reference to the pushed element.
+    ///
+    /// # Errors
+    /// If the StackVec is full, the function is not
+    /// called, and a [CapacityError] is returned.
+    ///
+    /// # Panics
+    /// - If the function returns a reference that doesn't point to the slot
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut frames = StackVec::<[u8; 4096], 2>::new();
+    ///
+    /// let frame = frames.push_with(|slot| slot.write([0; 4096])).unwrap();
+    /// frame[0] = 1;
+    ///
+    /// assert_eq!(frames[0][0], 1);
+    /// ```
+    pub fn push_with<F>(&mut self, f: F) -> Result<&mut T, CapacityError>
     where
-        I: IntoIterator<Item = T>,
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
     {
-        for elem in it.into_iter() {
-            self.push(elem)
+        if self.length >= CAP {
+            self.overflow("push_with");
+            return Err(CapacityError);
         }
+        let slot = &mut self.inner[self.length];
+        let slot_ptr = slot.as_mut_ptr();
+        let elem: *mut T = f(slot);
+        assert!(
+            ptr::eq(elem, slot_ptr),
+            "The function passed to push_with must initialize the given slot"
+        );
+        self.grow(1);
+        /* SAFETY: The only safe way to get a &mut T to the
+         * slot is to initialize it, so the element is valid */
+        Ok(unsafe { &mut *elem })
     }
 
-    /// Attempts to push all the elements from the iterator into this StackVec.
+    /// Returns the unused capacity of this StackVec, as a slice of
+    /// uninitialized elements.
+    ///
+    /// After initializing some of them, [set_len](Self::set_len) can
+    /// be used to mark them as part of the StackVec.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<u8, 8>::new();
+    /// sv.push(1);
+    ///
+    /// let spare = sv.spare_capacity_mut();
+    /// spare[0].write(2);
+    /// spare[1].write(3);
+    ///
+    /// unsafe { sv.set_len(3) };
+    /// assert_eq!(sv.as_slice(), &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        &mut self.inner[self.length..]
+    }
+
+    /// Sets the length of this StackVec.
+    ///
+    /// # Safety
+    /// - `len` must be less or equal than CAP
+    /// - The elements in [0, len) must be initialized
+    /// - If the length is reduced, the elements in [len, old_len)
+    ///   are leaked, unless the caller takes care of them.
+    #[inline]
+    pub unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= CAP);
+        if len > self.length {
+            self.grow(len - self.length);
+        } else {
+            self.length = len;
+        }
+    }
+
+    /// Initializes the next `n` elements of the spare capacity, with
+    /// the values returned by the function, which receives the index
+    /// of each element relative to the first one.
+    ///
+    /// The elements are written directly into the buffer, and
+    /// the length is updated once at the end.
+    ///
+    /// Returns a mutable slice to the new elements.
     ///
     /// # Errors
-    /// If the iterator yields more elements that we can push, returns the
-    /// iterator (turned into a [Peekable]) as an Err variant
-    pub fn try_extend_from_iter<I>(
-        &mut self,
-        it: I,
-    ) -> Result<(), Peekable<<I as IntoIterator>::IntoIter>>
+    /// If there's no room for `n` more elements, the function
+    /// is not called, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<u32, 8>::new();
+    /// sv.push(0);
+    ///
+    /// let squares = sv.init_spare_with(3, |i| (i * i) as u32).unwrap();
+    /// assert_eq!(squares, &[0, 1, 4]);
+    ///
+    /// assert_eq!(sv.as_slice(), &[0, 0, 1, 4]);
+    /// assert!(sv.init_spare_with(5, |_| 0).is_err());
+    /// ```
+    pub fn init_spare_with<F>(&mut self, n: usize, mut f: F) -> Result<&mut [T], CapacityError>
     where
-        I: IntoIterator<Item = T>,
+        F: FnMut(usize) -> T,
     {
-        let mut it = it.into_iter().peekable();
-        while it.peek().is_some() {
-            if self.length >= CAP {
-                return Err(it);
-            }
-            unsafe {
-                /* SAFETY:
-                 * 1) In the while condition, we've checked that the
-                 *    iterator has a next element.
-                 *
-                 * 2) In the condition above, we check that there's room
-                 *    for this element
-                 * */
-                let elem = it.next().unwrap_unchecked();
-                self.push_unchecked(elem)
+        self.ensure_remaining(n)?;
+
+        /* If f panics, the elements initialized so far are
+         * added to the StackVec, so they aren't leaked */
+        struct Guard<'a, T, const CAP: usize> {
+            sv: &'a mut StackVec<T, CAP>,
+            init: usize,
+        }
+
+        impl<T, const CAP: usize> Drop for Guard<'_, T, CAP> {
+            fn drop(&mut self) {
+                self.sv.grow(self.init);
             }
         }
+
+        let start = self.length;
+        let mut guard = Guard { sv: self, init: 0 };
+        let spare = guard.sv.spare_capacity_mut().as_mut_ptr();
+        while guard.init < n {
+            /* SAFETY: We've checked that there's room for n elements */
+            unsafe { (*spare.add(guard.init)).write(f(guard.init)) };
+            guard.init += 1;
+        }
+        drop(guard);
+
+        Ok(&mut self.as_slice_mut()[start..])
+    }
+
+    /// Clones the elements of the slice to the end of this StackVec.
+    ///
+    /// If T is a primitive type, the elements are copied with a memcpy.
+    /// Otherwise, they're cloned one by one, straight into the spare
+    /// capacity, and the length is updated once at the end.
+    fn extend_cloned(&mut self, src: &[T]) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        if !bitwise::is_bitwise_clone::<T>() {
+            return self
+                .init_spare_with(src.len(), |i| src[i].clone())
+                .map(|_| ());
+        }
+        self.ensure_remaining(src.len())?;
+        unsafe {
+            /* SAFETY: We've checked that there's room for the slice,
+             * and cloning a T is the same as copying its bytes */
+            ptr::copy_nonoverlapping(src.as_ptr(), self.as_mut_ptr().add(self.length), src.len());
+        }
+        self.grow(src.len());
         Ok(())
     }
 
-    /// Removes the ith element of the StackVec, and returns it.
+    /// Pushes `n` copies of `val`, doubling the copied block
+    /// each time, so it takes log(n) memcpys.
     ///
     /// # Safety
-    /// - i must be within bounds [0, [Self::len])
-    pub unsafe fn remove_unchecked(&mut self, i: usize) -> T {
-        /* SAFETY: self.inner[i] is initialized, thus reading
-         * from this pointer is safe */
-        let ret = unsafe { self.inner[i].assume_init_read() };
-
-        let ptr = self.inner.as_mut_ptr();
-
+    /// - Cloning a T must be the same as copying its bytes
+    /// - There must be room for `n` elements, and `n` must be greater than 0
+    unsafe fn fill_bitwise(&mut self, val: T, n: usize) {
         unsafe {
-            /* SAFETY: Elements [i + 1, len) are within bounds
-             * for the buffer, and can be copied over */
-            ptr::copy(ptr.add(i + 1), ptr.add(i), self.length - i - 1);
+            let start = self.as_mut_ptr().add(self.length);
+            ptr::write(start, val);
+            let mut filled = 1;
+            while filled < n {
+                let chunk = filled.min(n - filled);
+                ptr::copy_nonoverlapping(start, start.add(filled), chunk);
+                filled += chunk;
+            }
         }
-        self.length -= 1;
-        ret
+        self.grow(n);
     }
 
-    /// Removes the ith element of the StackVec, and returns it.
-    /// If the index is out of bounds, returns None
-    pub fn remove(&mut self, i: usize) -> Option<T> {
-        if i <= self.length {
-            unsafe { Some(self.remove_unchecked(i)) }
-        } else {
-            None
+    /// Clones the elements of the slice to the end of this StackVec.
+    ///
+    /// Primitive types are copied with a single memcpy,
+    /// instead of cloning the elements one by one.
+    ///
+    /// # Errors
+    /// If there's no room for the whole slice, nothing is
+    /// appended, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut names = StackVec::<String, 4>::new();
+    /// names.extend_from_slice(&["a".to_string(), "b".to_string()]).unwrap();
+    /// assert_eq!(names.as_slice(), &["a", "b"]);
+    ///
+    /// let mut bytes = StackVec::<u8, 4>::new();
+    /// bytes.extend_from_slice(b"abc").unwrap();
+    /// assert!(bytes.extend_from_slice(b"de").is_err());
+    /// assert_eq!(bytes.as_slice(), b"abc");
+    /// ```
+    #[inline]
+    pub fn extend_from_slice(&mut self, src: &[T]) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        self.extend_cloned(src)
+    }
+
+    /// Moves all the elements of the array into this StackVec.
+    ///
+    /// # Panics
+    /// - If there's no room for all the elements
+    #[inline]
+    #[track_caller]
+    pub fn extend_from_array<const N: usize>(&mut self, arr: [T; N]) {
+        if self.try_extend_from_array(arr).is_err() {
+            capacity_overflow()
+        }
+    }
+
+    /// Attempts to move all the elements of the array into this StackVec.
+    /// The elements are copied at once, instead of being pushed one by one.
+    ///
+    /// # Errors
+    /// If there's no room for all the elements, returns back
+    /// the array inside an Err variant.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<u8, 6>::new();
+    /// sv.try_extend_from_array([1, 2, 3, 4]).unwrap();
+    ///
+    /// assert_eq!(sv.as_slice(), &[1, 2, 3, 4]);
+    /// assert_eq!(sv.try_extend_from_array([5, 6, 7]), Err([5, 6, 7]));
+    /// ```
+    pub fn try_extend_from_array<const N: usize>(&mut self, arr: [T; N]) -> Result<(), [T; N]> {
+        if N > self.remaining_capacity() {
+            self.overflow("try_extend_from_array");
+            return Err(arr);
+        }
+        let arr = ManuallyDrop::new(arr);
+        unsafe {
+            /* SAFETY: We've checked that there's room for the elements,
+             * and the array won't be dropped, so they are moved */
+            ptr::copy_nonoverlapping(arr.as_ptr(), self.as_mut_ptr().add(self.length), N);
+        }
+        self.grow(N);
+        Ok(())
+    }
+
+    /// Attempts to copy all the given slices, one after the other,
+    /// to the end of this StackVec.
+    ///
+    /// The total length is checked once, and then each slice is
+    /// copied at once. This is useful to assemble a buffer from
+    /// several fragments, like a header, a payload and a trailer.
+    ///
+    /// # Errors
+    /// If there's no room for all the slices, nothing is
+    /// copied, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut frame = StackVec::<u8, 8>::new();
+    /// frame.extend_from_slices(&[&[0xAA], b"data", &[0x55]]).unwrap();
+    ///
+    /// assert_eq!(frame.as_slice(), &[0xAA, b'd', b'a', b't', b'a', 0x55]);
+    /// assert!(frame.extend_from_slices(&[b"ab", b"cd"]).is_err());
+    /// assert_eq!(frame.len(), 6);
+    /// ```
+    pub fn extend_from_slices(&mut self, slices: &[&[T]]) -> Result<(), CapacityError>
+    where
+        T: Copy,
+    {
+        let total = slices
+            .iter()
+            .try_fold(0usize, |acc, s| acc.checked_add(s.len()))
+            .ok_or(CapacityError)?;
+        self.ensure_remaining(total)?;
+        for s in slices {
+            unsafe {
+                /* SAFETY: We've checked that there's room for all the
+                 * slices, and T is Copy, so a bitwise copy is fine */
+                ptr::copy_nonoverlapping(s.as_ptr(), self.as_mut_ptr().add(self.length), s.len());
+            }
+            self.grow(s.len());
+        }
+        Ok(())
+    }
+
+    /// Attempts to append the given pattern `times` times.
+    ///
+    /// # Errors
+    /// If there's no room for all the repetitions, nothing is
+    /// appended, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// /* PKCS#7 padding */
+    /// let mut block = StackVec::<u8, 8>::new();
+    /// block.extend_from_iter(*b"hello");
+    /// let pad = block.remaining_capacity() as u8;
+    /// block.extend_repeating(&[pad], pad as usize).unwrap();
+    ///
+    /// assert_eq!(block.as_slice(), b"hello\x03\x03\x03");
+    /// ```
+    pub fn extend_repeating(&mut self, pattern: &[T], times: usize) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        let total = pattern.len().checked_mul(times).ok_or(CapacityError)?;
+        self.ensure_remaining(total)?;
+        for _ in 0..times {
+            /* We've checked that there's room for all the repetitions */
+            let _ = self.extend_cloned(pattern);
+        }
+        Ok(())
+    }
+
+    /// Pushes all the elements from the iterator into this StackVec.
+    #[inline]
+    pub fn extend_from_iter<I>(&mut self, it: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for elem in it.into_iter() {
+            self.push(elem)
+        }
+    }
+
+    /// Attempts to push all the elements from the iterator into this StackVec.
+    ///
+    /// # Errors
+    /// If the iterator yields more elements that we can push, returns the
+    /// iterator (turned into a [Peekable]) as an Err variant
+    pub fn try_extend_from_iter<I>(
+        &mut self,
+        it: I,
+    ) -> Result<(), Peekable<<I as IntoIterator>::IntoIter>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut it = it.into_iter().peekable();
+        while it.peek().is_some() {
+            if self.length >= CAP {
+                self.overflow("try_extend_from_iter");
+                return Err(it);
+            }
+            unsafe {
+                /* SAFETY:
+                 * 1) In the while condition, we've checked that the
+                 *    iterator has a next element.
+                 *
+                 * 2) In the condition above, we check that there's room
+                 *    for this element
+                 * */
+                let elem = it.next().unwrap_unchecked();
+                self.push_unchecked(elem)
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes clones of the given value until this StackVec is full
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut frame = StackVec::<u8, 6>::new();
+    /// frame.extend_from_iter([0xCA, 0xFE]);
+    /// frame.fill_to_capacity(0);
+    ///
+    /// assert_eq!(frame.as_slice(), &[0xCA, 0xFE, 0, 0, 0, 0]);
+    /// ```
+    pub fn fill_to_capacity(&mut self, val: T)
+    where
+        T: Clone,
+    {
+        if self.length == CAP {
+            return;
+        }
+        if bitwise::is_bitwise_clone::<T>() {
+            /* SAFETY: There's room for the remaining elements */
+            unsafe { self.fill_bitwise(val, CAP - self.length) };
+            return;
+        }
+        while self.length < CAP - 1 {
+            /* SAFETY: We've just checked that there's room for the element */
+            unsafe { self.push_unchecked(val.clone()) };
+        }
+        /* SAFETY: There's exactly one slot left */
+        unsafe { self.push_unchecked(val) };
+    }
+
+    /// Pushes values generated by the given function
+    /// until this StackVec is full
+    pub fn fill_to_capacity_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        while self.length < CAP {
+            /* SAFETY: We've just checked that there's room for the element */
+            unsafe { self.push_unchecked(f()) };
+        }
+    }
+
+    /// Resizes this StackVec to the given length.
+    ///
+    /// If `new_len` is greater than the current length, the StackVec
+    /// is extended with default values. Otherwise, it's truncated.
+    ///
+    /// # Errors
+    /// If `new_len` is greater than CAP, nothing is
+    /// changed, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut buf = StackVec::<u8, 8>::new();
+    /// buf.resize_default(4).unwrap();
+    /// assert_eq!(buf.as_slice(), &[0, 0, 0, 0]);
+    ///
+    /// buf.resize_default(2).unwrap();
+    /// assert_eq!(buf.len(), 2);
+    ///
+    /// assert!(buf.resize_default(9).is_err());
+    /// ```
+    pub fn resize_default(&mut self, new_len: usize) -> Result<(), CapacityError>
+    where
+        T: Default,
+    {
+        if new_len > CAP {
+            self.overflow("resize_default");
+            return Err(CapacityError);
+        }
+        if new_len < self.length {
+            self.truncate_to(new_len);
+        }
+        while self.length < new_len {
+            /* SAFETY: We've checked that new_len fits in the StackVec */
+            unsafe { self.push_unchecked(T::default()) };
+        }
+        Ok(())
+    }
+
+    /// Creates an iterator that pops the elements from the back
+    /// of this StackVec, as long as they match the predicate.
+    ///
+    /// The elements are yielded from last to first. The iterator stops
+    /// at the first element that doesn't match, which is kept. If the
+    /// iterator is dropped before being fully consumed, the remaining
+    /// elements are kept too.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut frame = StackVec::from_array([7, 3, 0, 0, 0]);
+    ///
+    /// /* Trim the trailing padding */
+    /// assert_eq!(frame.pop_while(|&b| b == 0).count(), 3);
+    /// assert_eq!(frame.as_slice(), &[7, 3]);
+    ///
+    /// let popped = frame.pop_while(|_| true).collect::<Vec<_>>();
+    /// assert_eq!(popped, [3, 7]);
+    /// ```
+    #[inline]
+    pub fn pop_while<F>(&mut self, pred: F) -> PopWhile<'_, T, F, CAP>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        PopWhile {
+            sv: self,
+            pred,
+            done: false,
+        }
+    }
+
+    /// Removes the ith element of the StackVec, and returns it.
+    ///
+    /// # Safety
+    /// - i must be less than [Self::len]
+    pub unsafe fn remove_unchecked(&mut self, i: usize) -> T {
+        /* SAFETY: self.inner[i] is initialized, thus reading
+         * from this pointer is safe */
+        let ret = unsafe { self.inner[i].assume_init_read() };
+
+        let ptr = self.inner.as_mut_ptr();
+
+        unsafe {
+            /* SAFETY: Elements [i + 1, len) are within bounds
+             * for the buffer, and can be copied over */
+            ptr::copy(ptr.add(i + 1), ptr.add(i), self.length - i - 1);
+        }
+        self.length -= 1;
+        self.poison(self.length, self.length + 1);
+        ret
+    }
+
+    /// Removes the ith element of the StackVec, and returns it.
+    ///
+    /// # Panics
+    /// - If the index is out of bounds
+    #[track_caller]
+    pub fn remove(&mut self, i: usize) -> T {
+        match self.try_remove(i) {
+            Some(elem) => elem,
+            None => index_out_of_bounds(i, self.length),
+        }
+    }
+
+    /// Removes the ith element of the StackVec, and returns it.
+    /// If the index is out of bounds, returns None
+    pub fn try_remove(&mut self, i: usize) -> Option<T> {
+        if i < self.length {
+            /* SAFETY: We've just checked that i is within bounds */
+            unsafe { Some(self.remove_unchecked(i)) }
+        } else {
+            None
         }
     }
 
@@ -237,76 +1071,1124 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
     /// If empty, returns None
     #[inline(always)]
     pub fn pop(&mut self) -> Option<T> {
-        self.remove(self.length)
+        self.length
+            .checked_sub(1)
+            .and_then(|last| self.try_remove(last))
+    }
+
+    /// Replaces the ith element of the StackVec with the given
+    /// value, and returns the old one.
+    /// If the index is out of bounds, returns None, and the
+    /// value is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3]);
+    ///
+    /// assert_eq!(sv.replace(1, 5), Some(2));
+    /// assert_eq!(sv.replace(3, 5), None);
+    /// assert_eq!(sv.as_slice(), &[1, 5, 3]);
+    /// ```
+    #[inline]
+    pub fn replace(&mut self, i: usize, val: T) -> Option<T> {
+        self.get_mut(i).map(|elem| mem::replace(elem, val))
+    }
+
+    /// Takes the ith element of the StackVec, leaving a default
+    /// value in its place. Unlike [remove](Self::remove), the
+    /// other elements are not shifted.
+    /// If the index is out of bounds, returns None
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3]);
+    ///
+    /// assert_eq!(sv.take(1), Some(2));
+    /// assert_eq!(sv.as_slice(), &[1, 0, 3]);
+    /// ```
+    #[inline]
+    pub fn take(&mut self, i: usize) -> Option<T>
+    where
+        T: Default,
+    {
+        self.get_mut(i).map(mem::take)
+    }
+
+    /// Returns an slice of T's from this StackVec, with all
+    /// the currently allocated elements.
+    pub const fn as_slice(&self) -> &[T] {
+        let (slice, _) = self.inner.split_at(self.length);
+        /* SAFETY:
+         * - The caller guarantees that items in range 0..self.len are initialized
+         * - MaybeUninit<T> and T have the same memory layout and alignment */
+        unsafe { &*(slice as *const [MaybeUninit<T>] as *const [T]) }
+    }
+
+    /// Returns a mutable slice of T's from this StackVec, with
+    /// all the currently allocated elements.
+    pub const fn as_slice_mut(&mut self) -> &mut [T] {
+        let (slice, _) = self.inner.split_at_mut(self.length);
+        /* SAFETY: Same as as_slice */
+        unsafe { &mut *(slice as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+
+    /// Returns a reference to the ith element, or None
+    /// if the index is out of bounds.
+    ///
+    /// Unlike the slice method, this one is const, and only accepts
+    /// an index. To get a subslice, use [as_slice](Self::as_slice).
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// static TABLE: StackVec<u8, 3> = StackVec::from_array([1, 2, 3]);
+    /// const SECOND: Option<&u8> = TABLE.get(1);
+    ///
+    /// assert_eq!(SECOND, Some(&2));
+    /// assert_eq!(TABLE.get(3), None);
+    /// ```
+    #[inline]
+    pub const fn get(&self, i: usize) -> Option<&T> {
+        if i < self.length {
+            /* SAFETY: Elements in [0, len) are initialized */
+            Some(unsafe { self.inner[i].assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the first element, or None if empty
+    ///
+    /// Unlike the slice method, this one is const.
+    #[inline]
+    pub const fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the last element, or None if empty
+    ///
+    /// Unlike the slice method, this one is const.
+    #[inline]
+    pub const fn last(&self) -> Option<&T> {
+        match self.length {
+            0 => None,
+            len => self.get(len - 1),
+        }
+    }
+
+    /// Splits the elements of this StackVec into arrays of N elements,
+    /// and a remainder slice with less than N elements.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let sv = StackVec::from_array([1, 2, 3, 4, 5]);
+    /// let (chunks, rest) = sv.as_chunks::<2>();
+    ///
+    /// assert_eq!(chunks, &[[1, 2], [3, 4]]);
+    /// assert_eq!(rest, &[5]);
+    /// ```
+    pub const fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        const { assert!(N > 0, "Chunk size must be greater than 0") };
+        let n_chunks = self.length / N;
+        let (chunks, rest) = self.as_slice().split_at(n_chunks * N);
+        /* SAFETY: chunks has exactly n_chunks * N elements,
+         * and [T; N] has the same alignment as T */
+        let chunks = unsafe { slice::from_raw_parts(chunks.as_ptr() as *const [T; N], n_chunks) };
+        (chunks, rest)
+    }
+
+    /// Same as [as_chunks](Self::as_chunks), but returns mutable slices
+    pub const fn as_chunks_mut<const N: usize>(&mut self) -> (&mut [[T; N]], &mut [T]) {
+        const { assert!(N > 0, "Chunk size must be greater than 0") };
+        let n_chunks = self.length / N;
+        let (chunks, rest) = self.as_slice_mut().split_at_mut(n_chunks * N);
+        /* SAFETY: Same as as_chunks */
+        let chunks =
+            unsafe { slice::from_raw_parts_mut(chunks.as_mut_ptr() as *mut [T; N], n_chunks) };
+        (chunks, rest)
+    }
+
+    /// Clears all the elements in this StackVec
+    pub fn clear(&mut self) {
+        let len = self.length;
+        let ptr = self.as_slice_mut() as *mut [T];
+        unsafe {
+            /* SAFETY
+             * We set length to 0 before calling drop_in_place.
+             * In case a Drop call fails, we're good.
+             */
+            self.length = 0;
+            ptr::drop_in_place(ptr);
+        }
+        self.poison(0, len);
+    }
+
+    /// Clears all the elements in this StackVec, passing each
+    /// of them, in order, to the given function.
+    ///
+    /// If the function panics, the remaining elements are dropped.
+    #[inline]
+    pub fn clear_with<F>(&mut self, f: F)
+    where
+        F: FnMut(T),
+    {
+        self.truncate_with(0, f);
+    }
+
+    /// Shortens this StackVec to the given length, passing each
+    /// of the removed elements, in order, to the given function.
+    /// If `len` is greater or equal than the current length, this has no effect.
+    ///
+    /// If the function panics, the remaining elements are dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4, 5]);
+    /// let mut removed = Vec::new();
+    ///
+    /// sv.truncate_with(2, |x| removed.push(x));
+    ///
+    /// assert_eq!(sv.as_slice(), &[1, 2]);
+    /// assert_eq!(removed, [3, 4, 5]);
+    /// ```
+    pub fn truncate_with<F>(&mut self, len: usize, f: F)
+    where
+        F: FnMut(T),
+    {
+        if len < self.length {
+            self.drain_unchecked(len, self.length).for_each(f);
+        }
+    }
+
+    /// Reports a failed operation due to lack of capacity.
+    /// Does nothing unless the `overflow-hook` feature is enabled.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    pub(crate) fn overflow(&self, operation: &'static str) {
+        #[cfg(feature = "overflow-hook")]
+        hook::report::<T>(CAP, self.length, operation);
+    }
+
+    /// Overwrites the (uninitialized) slots in [from, to) with [POISON_BYTE].
+    /// Does nothing unless the `poison` feature is enabled in a debug build.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    pub(crate) fn poison(&mut self, from: usize, to: usize) {
+        #[cfg(all(feature = "poison", debug_assertions))]
+        unsafe {
+            /* SAFETY: The caller guarantees that [from, to) is within
+             * the buffer, and those slots are not initialized */
+            let start = self.inner.as_mut_ptr().add(from) as *mut u8;
+            ptr::write_bytes(start, POISON_BYTE, (to - from) * mem::size_of::<T>());
+        }
+    }
+
+    /// Returns an adapter that implements [Display](core::fmt::Display),
+    /// showing the elements separated by the given string.
+    ///
+    /// The formatting options (width, precision, ...) are applied to
+    /// each element.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let sv = StackVec::from_array([1.0, 2.5, 3.25]);
+    ///
+    /// assert_eq!(format!("{}", sv.display_separated(", ")), "1, 2.5, 3.25");
+    /// assert_eq!(format!("{:.1}", sv.display_separated("|")), "1.0|2.5|3.2");
+    /// ```
+    #[inline]
+    pub fn display_separated<'a>(&'a self, sep: &'a str) -> DisplaySeparated<'a, T> {
+        DisplaySeparated {
+            elems: self.as_slice(),
+            sep,
+        }
+    }
+
+    /// Returns this StackVec's buffer as a *const T.
+    #[inline(always)]
+    pub const fn as_ptr(&self) -> *const T {
+        self.inner.as_ptr() as *const T
+    }
+
+    /// Returns this StackVec's buffer as a *mut T.
+    #[inline(always)]
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.inner.as_mut_ptr() as *mut T
+    }
+
+    /// Returns this StackVec's buffer as a [NonNull] pointer.
+    #[inline(always)]
+    pub const fn as_non_null(&mut self) -> NonNull<T> {
+        /* SAFETY: The buffer is never null */
+        unsafe { NonNull::new_unchecked(self.as_mut_ptr()) }
+    }
+
+    /// Returns the range of pointers spanning the elements of this StackVec.
+    ///
+    /// The end pointer is one past the last element.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<u32, 8>::new();
+    /// sv.extend_from_iter([1, 2, 3]);
+    ///
+    /// let range = sv.as_ptr_range();
+    ///
+    /// assert_eq!(unsafe { range.end.offset_from(range.start) }, 3);
+    /// ```
+    #[inline]
+    pub const fn as_ptr_range(&self) -> Range<*const T> {
+        let start = self.as_ptr();
+        /* SAFETY: The end pointer is within, or one past, the buffer */
+        let end = unsafe { start.add(self.length) };
+        start..end
+    }
+
+    /// Returns the range of mutable pointers spanning
+    /// the elements of this StackVec.
+    ///
+    /// The end pointer is one past the last element.
+    #[inline]
+    pub const fn as_mut_ptr_range(&mut self) -> Range<*mut T> {
+        let len = self.length;
+        let start = self.as_mut_ptr();
+        /* SAFETY: The end pointer is within, or one past, the buffer */
+        let end = unsafe { start.add(len) };
+        start..end
+    }
+
+    /// Converts the given range into a pair of [start, end) indices.
+    /// Returns None if the range is reversed or out of bounds.
+    fn try_resolve_range<R: RangeBounds<usize>>(&self, range: R) -> Option<(usize, usize)> {
+        use core::ops::Bound;
+
+        let start = match range.start_bound() {
+            Bound::Included(i) => *i,
+            Bound::Excluded(i) => i.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(i) => i.checked_add(1)?,
+            Bound::Excluded(i) => *i,
+            Bound::Unbounded => self.length,
+        };
+
+        (start <= end && end <= self.length).then_some((start, end))
+    }
+
+    /// Same as [try_resolve_range](Self::try_resolve_range), but panics on invalid ranges
+    #[track_caller]
+    fn resolve_range<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        match self.try_resolve_range(range) {
+            Some(r) => r,
+            None => invalid_range(self.length),
+        }
+    }
+
+    /// Drains elements in the given range from this StackVec.
+    /// Creates a [Drain] object, that iterates over the removed elements.
+    ///
+    /// # Panics
+    /// - If the start of the range is greater than its end
+    /// - If the end of the range is greater than the length
+    #[inline]
+    #[track_caller]
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, CAP> {
+        let (start, end) = self.resolve_range(range);
+        self.drain_unchecked(start, end)
+    }
+
+    /// Drains elements in the given range from this StackVec.
+    ///
+    /// Same as [drain](Self::drain), but returns None instead of panicking
+    /// if the range is invalid.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3]);
+    ///
+    /// assert!(sv.try_drain(2..1).is_none());
+    /// assert!(sv.try_drain(..4).is_none());
+    /// assert!(sv.try_drain(1..).unwrap().eq([2, 3]));
+    /// ```
+    pub fn try_drain<R: RangeBounds<usize>>(&mut self, range: R) -> Option<Drain<'_, T, CAP>> {
+        let (start, end) = self.try_resolve_range(range)?;
+        Some(self.drain_unchecked(start, end))
+    }
+
+    /// Creates a Drain for the range [start, end), which must be valid
+    #[inline]
+    fn drain_unchecked(&mut self, start: usize, end: usize) -> Drain<'_, T, CAP> {
+        Drain::new(self, start, end)
+    }
+
+    /// Replaces the elements in the given range with the ones
+    /// yielded by the iterator, and returns the removed elements.
+    ///
+    /// The number of elements yielded by the iterator doesn't
+    /// need to match the length of the range.
+    ///
+    /// # Panics
+    /// - If the range is reversed or out of bounds
+    ///
+    /// # Errors
+    /// If the new elements don't fit in the StackVec, it's left
+    /// unchanged, and a [CapacityError] is returned. The elements
+    /// taken from the iterator up to that point are dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<i32, 6>::new();
+    /// sv.extend_from_iter([1, 2, 3, 4]);
+    ///
+    /// let removed = sv.splice(1..3, [7, 8, 9]).unwrap();
+    /// assert_eq!(removed.as_slice(), &[2, 3]);
+    /// assert_eq!(sv.as_slice(), &[1, 7, 8, 9, 4]);
+    ///
+    /// assert!(sv.splice(..1, [0, 0, 0]).is_err());
+    /// assert_eq!(sv.as_slice(), &[1, 7, 8, 9, 4]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Result<Self, CapacityError>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        let (start, end) = self.resolve_range(range);
+
+        let mut removed = Self::new();
+        removed.extend_from_iter(self.drain(start..end));
+
+        let tail_len = self.length - start;
+        let tail_start = CAP - tail_len;
+
+        /* The pointer to the buffer is derived again after each call
+         * that borrows self mutably, since those invalidate it */
+        let base = self.as_mut_ptr();
+        unsafe {
+            /* SAFETY: Move the tail to the end of the buffer, to make
+             * room for the new elements. While we insert them, the tail
+             * is not accounted in the length, so if the iterator panics,
+             * the tail is just leaked */
+            ptr::copy(base.add(start), base.add(tail_start), tail_len);
+        }
+        self.length = start;
+
+        let mut overflowed = false;
+        for elem in replace_with {
+            if self.length == tail_start {
+                overflowed = true;
+                break;
+            }
+            /* SAFETY: We've just checked that the slot is before the tail */
+            unsafe { self.push_unchecked(elem) };
+        }
+
+        if overflowed {
+            self.overflow("splice");
+            self.truncate_to(start);
+            for elem in removed.drain(..) {
+                /* SAFETY: These elements were already in the StackVec */
+                unsafe { self.push_unchecked(elem) };
+            }
+        }
+
+        let base = self.as_mut_ptr();
+        unsafe {
+            /* SAFETY: Move the tail back, right after the last element */
+            ptr::copy(base.add(tail_start), base.add(self.length), tail_len);
+        }
+        self.length += tail_len;
+
+        if overflowed {
+            Err(CapacityError)
+        } else {
+            Ok(removed)
+        }
+    }
+
+    /// Splits this StackVec in two at the given index.
+    ///
+    /// Returns a new StackVec with the elements in [at, len),
+    /// leaving the ones in [0, at) in this StackVec.
+    ///
+    /// # Panics
+    /// - If `at` is greater than the length
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4]);
+    /// let tail = sv.split_off(1);
+    ///
+    /// assert_eq!(sv.as_slice(), &[1]);
+    /// assert_eq!(tail.as_slice(), &[2, 3, 4]);
+    /// ```
+    #[track_caller]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        if at > self.length {
+            index_out_of_bounds(at, self.length);
+        }
+        let mut other = Self::new();
+        other.extend_from_iter(self.drain_unchecked(at, self.length));
+        other
+    }
+
+    /// Removes the first `n` elements of this StackVec, and returns them
+    /// in a new StackVec. The remaining elements are moved to the front.
+    ///
+    /// This is the counterpart of [split_off](Self::split_off), useful
+    /// to consume data from the head of a buffer.
+    ///
+    /// # Panics
+    /// - If `n` is greater than the length
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4]);
+    /// let head = sv.split_to(3);
+    ///
+    /// assert_eq!(head.as_slice(), &[1, 2, 3]);
+    /// assert_eq!(sv.as_slice(), &[4]);
+    /// ```
+    #[track_caller]
+    pub fn split_to(&mut self, n: usize) -> Self {
+        if n > self.length {
+            index_out_of_bounds(n, self.length);
+        }
+        let mut other = Self::new();
+        other.extend_from_iter(self.drain_unchecked(0, n));
+        other
+    }
+
+    /// Returns an iterator over the pieces of this StackVec that
+    /// are separated by elements matching the predicate.
+    ///
+    /// Each piece is cloned into its own StackVec. The separators
+    /// are not included, and consecutive separators yield empty
+    /// pieces, like [slice::split] does.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let line = StackVec::from_array(*b"set led on");
+    /// let mut words = line.split_on(|&b| b == b' ');
+    ///
+    /// assert_eq!(words.next().unwrap().as_slice(), b"set");
+    /// assert_eq!(words.next().unwrap().as_slice(), b"led");
+    /// assert_eq!(words.next().unwrap().as_slice(), b"on");
+    /// assert!(words.next().is_none());
+    /// ```
+    #[inline]
+    pub fn split_on<F>(&self, pred: F) -> SplitOn<'_, T, F, CAP>
+    where
+        T: Clone,
+        F: FnMut(&T) -> bool,
+    {
+        SplitOn {
+            inner: self.as_slice().split(pred),
+        }
+    }
+
+    /// Moves the elements in the given range, so that
+    /// the first of them ends up at index `dst`.
+    ///
+    /// The elements outside of the range keep their relative order.
+    /// This is done with a single rotation of the affected span, so
+    /// it's linear in the distance moved, instead of removing and
+    /// inserting each element.
+    ///
+    /// # Panics
+    /// - If the range is invalid
+    /// - If the moved block doesn't fit at `dst`, this is,
+    ///   if `dst` plus the length of the range is greater than
+    ///   the length of the StackVec
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut cmds = StackVec::from_array(['a', 'b', 'c', 'd', 'e']);
+    ///
+    /// cmds.move_range(1..3, 3);
+    /// assert_eq!(cmds.as_slice(), &['a', 'd', 'e', 'b', 'c']);
+    ///
+    /// cmds.move_range(3.., 0);
+    /// assert_eq!(cmds.as_slice(), &['b', 'c', 'a', 'd', 'e']);
+    /// ```
+    #[track_caller]
+    pub fn move_range<R: RangeBounds<usize>>(&mut self, range: R, dst: usize) {
+        let (start, end) = self.resolve_range(range);
+        let n = end - start;
+        if dst > self.length - n {
+            index_out_of_bounds(dst, self.length - n);
+        }
+        let slice = self.as_slice_mut();
+        if dst < start {
+            slice[dst..end].rotate_right(n);
+        } else if dst > start {
+            slice[start..dst + n].rotate_left(n);
+        }
+    }
+
+    /// Exchanges the contents of this StackVec with another one,
+    /// which may have a different capacity.
+    ///
+    /// # Errors
+    /// If the elements of either StackVec don't fit in the other one,
+    /// nothing is swapped, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut front = StackVec::<i32, 4>::from_array([1, 2, 3, 4]);
+    /// let mut back = StackVec::<i32, 8>::new();
+    /// back.extend_from_iter([5, 6]);
+    ///
+    /// front.swap_with(&mut back).unwrap();
+    /// assert_eq!(front.as_slice(), &[5, 6]);
+    /// assert_eq!(back.as_slice(), &[1, 2, 3, 4]);
+    ///
+    /// back.extend_from_iter([7, 8]);
+    /// assert!(front.swap_with(&mut back).is_err());
+    /// ```
+    pub fn swap_with<const OTHER: usize>(
+        &mut self,
+        other: &mut StackVec<T, OTHER>,
+    ) -> Result<(), CapacityError> {
+        if self.length > OTHER {
+            other.overflow("swap_with");
+            return Err(CapacityError);
+        }
+        if other.length > CAP {
+            self.overflow("swap_with");
+            return Err(CapacityError);
+        }
+
+        let (a, b) = (self.length, other.length);
+        let common = a.min(b);
+        unsafe {
+            /* SAFETY: Both StackVecs have at least `common` elements, and
+             * we've checked that the extra ones fit in the other StackVec */
+            ptr::swap_nonoverlapping(self.as_mut_ptr(), other.as_mut_ptr(), common);
+            if a > b {
+                ptr::copy_nonoverlapping(self.as_ptr().add(b), other.as_mut_ptr().add(b), a - b);
+            } else {
+                ptr::copy_nonoverlapping(other.as_ptr().add(a), self.as_mut_ptr().add(a), b - a);
+            }
+        }
+
+        if a > b {
+            self.length = b;
+            self.poison(b, a);
+            other.grow(a - b);
+        } else {
+            other.length = a;
+            other.poison(a, b);
+            self.grow(b - a);
+        }
+        Ok(())
+    }
+
+    /// Drops the elements after the first `len`
+    fn truncate_to(&mut self, len: usize) {
+        let old_len = self.length;
+        let tail =
+            ptr::slice_from_raw_parts_mut(unsafe { self.as_mut_ptr().add(len) }, old_len - len);
+        self.length = len;
+        /* SAFETY: Elements in [len, old_len) are initialized,
+         * and we've set the length before, in case a Drop panics */
+        unsafe { ptr::drop_in_place(tail) };
+        self.poison(len, old_len);
+    }
+
+    /// Creates a [CursorMut] pointing to the first element of this StackVec.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<i32, 8>::new();
+    /// sv.extend_from_iter([1, 2, 3, 4]);
+    ///
+    /// /* Remove the even numbers, and duplicate the odd ones */
+    /// let mut cursor = sv.cursor_mut();
+    /// while let Some(n) = cursor.current() {
+    ///     if *n % 2 == 0 {
+    ///         cursor.remove_current();
+    ///     } else {
+    ///         let n = *n;
+    ///         cursor.insert_before(n).unwrap();
+    ///         cursor.move_next();
+    ///     }
+    /// }
+    /// drop(cursor);
+    ///
+    /// assert_eq!(sv.as_slice(), &[1, 1, 3, 3]);
+    /// ```
+    #[inline]
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, CAP> {
+        CursorMut::new(self)
+    }
+
+    /// Creates an iterator which uses a closure to determine if an
+    /// element should be removed.
+    ///
+    /// If the closure returns true, the element is removed and
+    /// yielded. Otherwise, it stays in the StackVec. The elements
+    /// that are kept are compacted in a single pass, so this is O(n).
+    ///
+    /// If the iterator is dropped before being fully consumed,
+    /// the remaining elements are kept.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4, 5, 6]);
+    ///
+    /// let evens = sv.extract_if(|x| *x % 2 == 0).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(evens, [2, 4, 6]);
+    /// assert_eq!(sv.as_slice(), &[1, 3, 5]);
+    /// ```
+    #[inline]
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F, CAP>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
+    /// Retains only the elements for which the predicate returns true,
+    /// preserving their order.
+    ///
+    /// Returns the number of removed elements.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(sv.retain(|x| *x != 2 && *x != 4), 2);
+    /// assert_eq!(sv.as_slice(), &[1, 3, 5]);
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|x| f(x))
+    }
+
+    /// Same as [retain](Self::retain), but the predicate
+    /// receives a mutable reference to the elements.
+    ///
+    /// Returns the number of removed elements.
+    pub fn retain_mut<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.extract_if(|x| !f(x)).count()
+    }
+
+    /// Same as [retain](Self::retain), but the predicate also
+    /// receives the index of each element, before any removal.
+    ///
+    /// Returns the number of removed elements.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut samples = StackVec::from_array([10, 11, 12, 13, 14, 15]);
+    ///
+    /// /* Keep every third sample */
+    /// samples.retain_indexed(|i, _| i % 3 == 0);
+    /// assert_eq!(samples.as_slice(), &[10, 13]);
+    /// ```
+    pub fn retain_indexed<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(usize, &T) -> bool,
+    {
+        let mut i = 0;
+        self.retain(|x| {
+            i += 1;
+            f(i - 1, x)
+        })
+    }
+
+    /// Replaces each element with the result of the given function,
+    /// or removes it if the function returns None, in a single pass.
+    ///
+    /// Returns the number of removed elements.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4, 5]);
+    ///
+    /// let removed = sv.retain_map(|x| (x % 2 == 1).then_some(x * 10));
+    /// assert_eq!(removed, 2);
+    /// assert_eq!(sv.as_slice(), &[10, 30, 50]);
+    /// ```
+    pub fn retain_map<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(T) -> Option<T>,
+    {
+        let len = self.length;
+        let mut g = Compact::new(self, 0);
+        let base = g.sv.as_mut_ptr();
+
+        while g.read < len {
+            unsafe {
+                /* SAFETY: The element is initialized, and once read, it's
+                 * out of the [read, len) range, so it's not used again */
+                let elem = ptr::read(base.add(g.read));
+                g.read += 1;
+                if let Some(elem) = f(elem) {
+                    base.add(g.write).write(elem);
+                    g.write += 1;
+                }
+            }
+        }
+        len - g.write
+    }
+
+    /// Retains only the elements for which the predicate returns true.
+    ///
+    /// Each removed element is replaced by the last one, instead of
+    /// shifting all the elements after it. So this does less moves
+    /// than [retain](Self::retain), but the order is not preserved.
+    ///
+    /// Returns the number of removed elements.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(sv.retain_swap(|x| *x % 2 == 1), 2);
+    /// assert_eq!(sv.as_slice(), &[1, 5, 3]);
+    /// ```
+    pub fn retain_swap<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.length;
+        let mut i = 0;
+        while i < self.length {
+            if f(&mut self[i]) {
+                i += 1;
+            } else {
+                let last = self.length - 1;
+                self.swap(i, last);
+                self.truncate_to(last);
+            }
+        }
+        old_len - self.length
+    }
+
+    /// Removes consecutive elements that are considered equal by the
+    /// given function, keeping only the first of them.
+    ///
+    /// The function receives the element being checked and the last
+    /// kept element, in that order. If it returns true, the first one
+    /// is removed.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.length;
+        if len <= 1 {
+            return;
+        }
+
+        let mut g = Compact::new(self, 1);
+        let base = g.sv.as_mut_ptr();
+
+        while g.read < len {
+            unsafe {
+                /* SAFETY: Both elements are initialized, and are different.
+                 * If same_bucket or a Drop panics, the guard keeps the
+                 * unchecked elements */
+                let cur = base.add(g.read);
+                let prev = base.add(g.write - 1);
+                let same = same_bucket(&mut *cur, &mut *prev);
+                g.read += 1;
+                if same {
+                    ptr::drop_in_place(cur);
+                } else {
+                    ptr::copy(cur, base.add(g.write), 1);
+                    g.write += 1;
+                }
+            }
+        }
     }
 
-    /// Returns an slice of T's from this StackVec, with all
-    /// the currently allocated elements.
-    pub const fn as_slice(&self) -> &[T] {
-        let (slice, _) = self.inner.split_at(self.length);
-        /* SAFETY:
-         * - The caller guarantees that items in range 0..self.len are initialized
-         * - MaybeUninit<T> and T have the same memory layout and alignment */
-        unsafe { &*(slice as *const [MaybeUninit<T>] as *const [T]) }
+    /// Removes consecutive elements that map to the same key,
+    /// keeping only the first of them.
+    #[inline]
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
     }
 
-    /// Returns a mutable slice of T's from this StackVec, with
-    /// all the currently allocated elements.
-    pub const fn as_slice_mut(&mut self) -> &mut [T] {
-        let (slice, _) = self.inner.split_at_mut(self.length);
-        /* SAFETY: Same as as_slice */
-        unsafe { &mut *(slice as *mut [MaybeUninit<T>] as *mut [T]) }
+    /// Removes consecutive equal elements, keeping only the first of them.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([1, 1, 2, 3, 3, 3, 1]);
+    /// sv.dedup();
+    ///
+    /// assert_eq!(sv.as_slice(), &[1, 2, 3, 1]);
+    /// ```
+    #[inline]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
     }
 
-    /// Clears all the elements in this StackVec
-    pub fn clear(&mut self) {
-        let ptr = self.as_slice_mut() as *mut [T];
-        unsafe {
-            /* SAFETY
-             * We set length to 0 before calling drop_in_place.
-             * In case a Drop call fails, we're good.
-             */
-            self.length = 0;
-            ptr::drop_in_place(ptr);
+    /// Merges adjacent elements in place.
+    ///
+    /// The function receives the last kept element and the next one.
+    /// If it returns Ok, the merged element replaces both of them, and
+    /// it's passed again along with the following element. If it returns
+    /// Err, the two elements are kept, and the second one is compared
+    /// with the next.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut ranges = StackVec::from_array([0..2, 2..5, 7..8, 8..9, 12..13]);
+    /// ranges.coalesce(|a, b| {
+    ///     if a.end == b.start {
+    ///         Ok(a.start..b.end)
+    ///     } else {
+    ///         Err((a, b))
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(ranges.as_slice(), &[0..5, 7..9, 12..13]);
+    /// ```
+    pub fn coalesce<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T, T) -> Result<T, (T, T)>,
+    {
+        let len = self.length;
+        if len <= 1 {
+            return;
         }
-    }
 
-    /// Returns this StackVec's buffer as a *const T.
-    #[inline(always)]
-    pub const fn as_ptr(&self) -> *const T {
-        self.inner.as_ptr() as *const T
-    }
+        let mut g = Compact::new(self, 1);
+        let base = g.sv.as_mut_ptr();
 
-    /// Returns this StackVec's buffer as a *mut T.
-    #[inline(always)]
-    pub const fn as_mut_ptr(&mut self) -> *mut T {
-        self.inner.as_mut_ptr() as *mut T
+        while g.read < len {
+            unsafe {
+                /* SAFETY: Both elements are initialized. They're moved out
+                 * of the kept and unchecked regions before calling f, so
+                 * if it panics, the guard doesn't touch them */
+                g.write -= 1;
+                let prev = ptr::read(base.add(g.write));
+                let cur = ptr::read(base.add(g.read));
+                g.read += 1;
+                /* There are, at least, two free slots at write */
+                match f(prev, cur) {
+                    Ok(merged) => {
+                        ptr::write(base.add(g.write), merged);
+                        g.write += 1;
+                    }
+                    Err((a, b)) => {
+                        ptr::write(base.add(g.write), a);
+                        ptr::write(base.add(g.write + 1), b);
+                        g.write += 2;
+                    }
+                }
+            }
+        }
     }
 
-    /// Drains elements in the given range from this StackVec.
-    /// Creates a [Drain] object, that iterates over the removed elements.
-    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, CAP> {
-        use core::ops::Bound;
+    /// Sorts this StackVec, and removes the duplicated elements.
+    ///
+    /// The sort is unstable, so it's not specified which
+    /// one of the equal elements is kept.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut ids = StackVec::from_array([4, 1, 4, 2, 1]);
+    /// ids.sort_unstable_and_dedup();
+    ///
+    /// assert_eq!(ids.as_slice(), &[1, 2, 4]);
+    /// ```
+    #[inline]
+    pub fn sort_unstable_and_dedup(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_unstable();
+        self.dedup();
+    }
 
-        let start = match range.start_bound() {
-            Bound::Included(i) => *i,
-            Bound::Excluded(i) => *i + 1,
-            Bound::Unbounded => 0,
-        };
+    /// Sorts this StackVec by the given key, and removes the
+    /// elements with duplicated keys.
+    ///
+    /// The sort is unstable, so it's not specified which one
+    /// of the elements with the same key is kept.
+    #[inline]
+    pub fn sort_unstable_and_dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.sort_unstable_by_key(&mut key);
+        self.dedup_by_key(|x| key(x));
+    }
 
-        let end = match range.end_bound() {
-            Bound::Included(i) => *i + 1,
-            Bound::Excluded(i) => *i,
-            Bound::Unbounded => self.length,
-        };
+    /// Collapses the runs of consecutive equal elements of this
+    /// StackVec into (element, count) pairs.
+    ///
+    /// The first element of each run is kept, and the rest are dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let readings = StackVec::from_array([0, 0, 0, 5, 5, 0]);
+    /// let runs = readings.group_runs();
+    ///
+    /// assert_eq!(runs.as_slice(), &[(0, 3), (5, 2), (0, 1)]);
+    /// ```
+    pub fn group_runs(self) -> StackVec<(T, usize), CAP>
+    where
+        T: PartialEq,
+    {
+        let mut runs = StackVec::<(T, usize), CAP>::new();
+        for elem in self {
+            match runs.last_mut() {
+                Some((last, n)) if *last == elem => *n += 1,
+                /* SAFETY: There can't be more runs than elements */
+                _ => unsafe { runs.push_unchecked((elem, 1)) },
+            }
+        }
+        runs
+    }
 
-        /* SAFETY: A reference is always non null */
-        let sv = unsafe { NonNull::new_unchecked(self) };
+    /// Combines this StackVec with another one of the same length,
+    /// calling the given function on each pair of elements.
+    ///
+    /// # Panics
+    /// - If the lengths of both StackVecs are different
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let a = StackVec::from_array([1, 2, 3]);
+    /// let b = StackVec::from_array([10, 20, 30]);
+    ///
+    /// let sum = a.zip_with(b, |x, y| x + y);
+    /// assert_eq!(sum.as_slice(), &[11, 22, 33]);
+    /// ```
+    #[track_caller]
+    pub fn zip_with<U, C, F>(self, other: StackVec<U, CAP>, mut f: F) -> StackVec<C, CAP>
+    where
+        F: FnMut(T, U) -> C,
+    {
+        if self.length != other.length {
+            length_mismatch(self.length, other.length);
+        }
+        let mut sv = StackVec::new();
+        for (a, b) in self.into_iter().zip(other) {
+            /* SAFETY: There are at most CAP pairs */
+            unsafe { sv.push_unchecked(f(a, b)) };
+        }
+        sv
+    }
 
-        let iter = self.as_slice()[start..end].iter();
-        let len = end - start;
+    /// Replaces each element with the result of calling the given
+    /// function on the previous (already replaced) element and itself.
+    ///
+    /// The first element is combined with `init`.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array([3, 1, 4, 1, 5]);
+    /// sv.scan_in_place(0, |max, x| *max.max(x));
+    ///
+    /// assert_eq!(sv.as_slice(), &[3, 3, 4, 4, 5]);
+    /// ```
+    pub fn scan_in_place<F>(&mut self, init: T, mut f: F)
+    where
+        F: FnMut(&T, &T) -> T,
+    {
+        let slice = self.as_slice_mut();
+        if let Some(first) = slice.first_mut() {
+            *first = f(&init, first);
+        }
+        for i in 1..slice.len() {
+            let (prev, rest) = slice.split_at_mut(i);
+            rest[0] = f(&prev[i - 1], &rest[0]);
+        }
+    }
 
-        Drain::new(sv, iter, start, len)
+    /// Replaces each element with the sum of itself
+    /// and all the elements before it.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut counts = StackVec::from_array([2, 0, 3, 1]);
+    /// counts.prefix_sums();
+    ///
+    /// assert_eq!(counts.as_slice(), &[2, 2, 5, 6]);
+    /// ```
+    pub fn prefix_sums(&mut self)
+    where
+        T: Copy + AddAssign,
+    {
+        let slice = self.as_slice_mut();
+        for i in 1..slice.len() {
+            let prev = slice[i - 1];
+            slice[i] += prev;
+        }
     }
 
     /// Tries to convert this StackVector into an array of T.
@@ -323,6 +2205,44 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
         }
     }
 
+    /// Asserts, at compile time, that the capacity is at least N.
+    ///
+    /// This is useful when the number of elements to push is known
+    /// statically, to get a build error instead of a runtime panic.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// type Header = StackVec<u8, 16>;
+    ///
+    /// Header::assert_capacity_at_least::<12>();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use stack_vector::StackVec;
+    ///
+    /// StackVec::<u8, 8>::assert_capacity_at_least::<12>();
+    /// ```
+    #[inline(always)]
+    pub const fn assert_capacity_at_least<const N: usize>() {
+        const { assert!(CAP >= N, "The capacity of the StackVec is less than N") }
+    }
+
+    /// Asserts, at compile time, that the capacity is at most N.
+    ///
+    /// This is useful to bound the stack usage of a StackVec.
+    ///
+    /// ```compile_fail
+    /// use stack_vector::StackVec;
+    ///
+    /// StackVec::<u8, 8>::assert_capacity_at_most::<4>();
+    /// ```
+    #[inline(always)]
+    pub const fn assert_capacity_at_most<const N: usize>() {
+        const { assert!(CAP <= N, "The capacity of the StackVec is greater than N") }
+    }
+
     /// Returns the capacity of this StackVec.
     /// This is just a convenience function, since the
     /// capacity is a const generic argument.
@@ -338,6 +2258,61 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
         CAP - self.length
     }
 
+    /// Checks that there's room for at least `n` more elements.
+    ///
+    /// This is useful to check up front that a sequence of
+    /// pushes will succeed, before starting it.
+    ///
+    /// # Errors
+    /// If the remaining capacity is less than `n`
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<u8, 4>::new();
+    /// sv.push(1);
+    ///
+    /// assert!(sv.ensure_remaining(3).is_ok());
+    /// assert!(sv.ensure_remaining(4).is_err());
+    /// ```
+    pub fn ensure_remaining(&self, n: usize) -> Result<(), CapacityError> {
+        if n > self.remaining_capacity() {
+            self.overflow("ensure_remaining");
+            Err(CapacityError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the maximum length this StackVec has ever reached.
+    ///
+    /// This is useful to size the capacity of a buffer empirically.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<i32, 10>::new();
+    /// sv.extend_from_iter([1, 2, 3]);
+    /// sv.clear();
+    /// sv.push(4);
+    ///
+    /// assert_eq!(sv.high_water_mark(), 3);
+    /// ```
+    #[cfg(feature = "high-water-mark")]
+    #[inline(always)]
+    pub const fn high_water_mark(&self) -> usize {
+        self.high_water
+    }
+
+    /// Resets the high water mark to the current length
+    #[cfg(feature = "high-water-mark")]
+    #[inline(always)]
+    pub const fn reset_high_water_mark(&mut self) {
+        self.high_water = self.length;
+    }
+
     /// Returns the length of this StackVec, this is, the
     /// number of elements "pushed" into it.
     #[inline(always)]
@@ -358,6 +2333,68 @@ impl<T, const CAP: usize> StackVec<T, CAP> {
     }
 }
 
+/// A guard used to remove elements from a StackVec in a single pass.
+///
+/// Elements in [0, write) are kept, and the ones in [read, len) haven't
+/// been checked yet. While this guard is alive, the elements in between
+/// are either moved out or dropped. When it's dropped, even by a panic,
+/// the unchecked elements are moved after the kept ones.
+struct Compact<'a, T, const CAP: usize> {
+    sv: &'a mut StackVec<T, CAP>,
+    read: usize,
+    write: usize,
+    len: usize,
+}
+
+impl<'a, T, const CAP: usize> Compact<'a, T, CAP> {
+    /// Starts compacting the StackVec, keeping its first `start` elements
+    fn new(sv: &'a mut StackVec<T, CAP>, start: usize) -> Self {
+        let len = sv.length;
+        /* If the guard is leaked, the elements are leaked too */
+        sv.length = 0;
+        Self {
+            sv,
+            read: start,
+            write: start,
+            len,
+        }
+    }
+}
+
+impl<T, const CAP: usize> Drop for Compact<'_, T, CAP> {
+    fn drop(&mut self) {
+        let base = self.sv.as_mut_ptr();
+        let rest = self.len - self.read;
+        unsafe {
+            /* SAFETY: Both ranges are within the buffer */
+            ptr::copy(base.add(self.read), base.add(self.write), rest);
+        }
+        self.sv.length = self.write + rest;
+        self.sv.poison(self.write + rest, self.len);
+    }
+}
+
+impl<T, const CAP: usize> StackVec<Option<T>, CAP> {
+    /// Removes the None elements, and unwraps the Some ones.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let results = StackVec::from_array([Some(1), None, Some(3), None]);
+    ///
+    /// assert_eq!(results.filter_some().as_slice(), &[1, 3]);
+    /// ```
+    pub fn filter_some(self) -> StackVec<T, CAP> {
+        let mut sv = StackVec::new();
+        for elem in self.into_iter().flatten() {
+            /* SAFETY: There are at most CAP elements */
+            unsafe { sv.push_unchecked(elem) };
+        }
+        sv
+    }
+}
+
 impl<T, const CAP: usize> Deref for StackVec<T, CAP> {
     type Target = [T];
 
@@ -388,6 +2425,67 @@ impl<T, const CAP: usize> From<[T; CAP]> for StackVec<T, CAP> {
     }
 }
 
+/// Appends the UTF-8 bytes of the written strings.
+///
+/// If a string doesn't fit, nothing is appended, and an error is returned.
+///
+/// # Example
+/// ```
+/// use core::fmt::Write;
+/// use stack_vector::StackVec;
+///
+/// let mut buf = StackVec::<u8, 8>::new();
+///
+/// write!(buf, "id={}", 42).unwrap();
+/// assert_eq!(buf.as_slice(), b"id=42");
+///
+/// assert!(write!(buf, "{}", 1234).is_err());
+/// assert_eq!(buf.as_slice(), b"id=42");
+/// ```
+impl<const CAP: usize> core::fmt::Write for StackVec<u8, CAP> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.ensure_remaining(s.len())
+            .map_err(|_| core::fmt::Error)?;
+        unsafe {
+            /* SAFETY: We've just checked that there's room for the bytes */
+            ptr::copy_nonoverlapping(s.as_ptr(), self.as_mut_ptr().add(self.length), s.len());
+        }
+        self.grow(s.len());
+        Ok(())
+    }
+}
+
+impl<T, const CAP: usize> IntoIterator for StackVec<T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a StackVec<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a mut StackVec<T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(not(feature = "may-dangle"))]
 impl<T, const CAP: usize> Drop for StackVec<T, CAP> {
     fn drop(&mut self) {
         if mem::needs_drop::<T>() {
@@ -396,18 +2494,27 @@ impl<T, const CAP: usize> Drop for StackVec<T, CAP> {
     }
 }
 
+/* SAFETY: The only thing we do with the elements on drop is dropping
+ * them, and the PhantomData<T> tells the drop checker about it */
+#[cfg(feature = "may-dangle")]
+unsafe impl<#[may_dangle] T, const CAP: usize> Drop for StackVec<T, CAP> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            self.clear();
+        }
+    }
+}
+
 impl<T: Clone, const CAP: usize> Clone for StackVec<T, CAP> {
     fn clone(&self) -> Self {
-        let mut inner = [const { MaybeUninit::uninit() }; CAP];
-        let src = self.inner.as_ptr();
-        let dst = inner.as_mut_ptr();
-        unsafe {
-            ptr::copy(src, dst, self.length);
-        }
-        Self {
-            inner,
-            length: self.length,
+        let mut sv = Self::new();
+        /* The clone has the same length, so it always fits */
+        let _ = sv.extend_cloned(self);
+        #[cfg(feature = "high-water-mark")]
+        {
+            sv.high_water = self.high_water;
         }
+        sv
     }
 }
 
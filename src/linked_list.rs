@@ -0,0 +1,437 @@
+use core::iter::FusedIterator;
+
+/// A handle to an element of a [StackLinkedList].
+///
+/// Like the [keys](crate::slot_map::Key) of a [StackSlotMap](crate::StackSlotMap),
+/// a handle stores the generation of its node, so it becomes stale when
+/// its element is removed, even if the node is reused by another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+impl Handle {
+    /// Returns the index of the node this handle points to
+    #[inline(always)]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+struct Node<T> {
+    generation: u32,
+    prev: usize,
+    /* For vacant nodes, this is the next node in the free-list */
+    next: usize,
+    val: Option<T>,
+}
+
+/// A fixed-capacity doubly linked list.
+///
+/// The nodes live in an inline array, and link to each other
+/// by index. Elements are accessed through [handles](Handle),
+/// which allow inserting and removing at any position in O(1),
+/// without shifting the other elements like a [StackVec](crate::StackVec)
+/// would.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackLinkedList;
+///
+/// let mut list = StackLinkedList::<&str, 4>::new();
+///
+/// let b = list.push_back("b").unwrap();
+/// list.push_front("a").unwrap();
+/// let d = list.push_back("d").unwrap();
+/// list.insert_after(b, "c").unwrap();
+///
+/// assert!(list.iter().map(|(_, s)| *s).eq(["a", "b", "c", "d"]));
+///
+/// assert_eq!(list.remove(b), Some("b"));
+/// assert!(list.move_to_front(d));
+/// assert!(list.iter().map(|(_, s)| *s).eq(["d", "a", "c"]));
+///
+/// /* b was removed, so its handle is stale */
+/// assert_eq!(list.get(b), None);
+/// ```
+pub struct StackLinkedList<T, const CAP: usize> {
+    nodes: [Node<T>; CAP],
+    head: usize,
+    tail: usize,
+    /* Head of the free-list */
+    free_head: usize,
+    /* Nodes in [used, CAP) have never been occupied */
+    used: usize,
+    length: usize,
+}
+
+impl<T, const CAP: usize> StackLinkedList<T, CAP> {
+    /// Creates a new empty StackLinkedList
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            nodes: [const {
+                Node {
+                    generation: 0,
+                    prev: NONE,
+                    next: NONE,
+                    val: None,
+                }
+            }; CAP],
+            head: NONE,
+            tail: NONE,
+            free_head: NONE,
+            used: 0,
+            length: 0,
+        }
+    }
+
+    #[inline]
+    fn handle(&self, index: usize) -> Handle {
+        Handle {
+            index,
+            generation: self.nodes[index].generation,
+        }
+    }
+
+    /// Returns the index of the node the handle points
+    /// to, or None if the handle is stale
+    fn index_of(&self, handle: Handle) -> Option<usize> {
+        self.nodes
+            .get(handle.index)
+            .filter(|n| n.generation == handle.generation && n.val.is_some())
+            .map(|_| handle.index)
+    }
+
+    /// Takes a vacant node, and stores the value in it
+    fn alloc(&mut self, val: T) -> Result<usize, T> {
+        let index = if self.free_head != NONE {
+            let index = self.free_head;
+            self.free_head = self.nodes[index].next;
+            index
+        } else if self.used < CAP {
+            self.used += 1;
+            self.used - 1
+        } else {
+            return Err(val);
+        };
+        self.nodes[index].val = Some(val);
+        self.length += 1;
+        Ok(index)
+    }
+
+    /// Takes the value out of an (unlinked) node, and
+    /// adds the node to the free-list
+    fn free(&mut self, index: usize) -> T {
+        let node = &mut self.nodes[index];
+        let val = node.val.take().unwrap();
+        node.generation = node.generation.wrapping_add(1);
+        node.next = self.free_head;
+        self.free_head = index;
+        self.length -= 1;
+        val
+    }
+
+    /// Links the node between `prev` and `next`
+    fn link(&mut self, index: usize, prev: usize, next: usize) {
+        self.nodes[index].prev = prev;
+        self.nodes[index].next = next;
+        match prev {
+            NONE => self.head = index,
+            _ => self.nodes[prev].next = index,
+        }
+        match next {
+            NONE => self.tail = index,
+            _ => self.nodes[next].prev = index,
+        }
+    }
+
+    /// Removes the node from the list, linking its neighbours together
+    fn unlink(&mut self, index: usize) {
+        let Node { prev, next, .. } = self.nodes[index];
+        match prev {
+            NONE => self.head = next,
+            _ => self.nodes[prev].next = next,
+        }
+        match next {
+            NONE => self.tail = prev,
+            _ => self.nodes[next].prev = prev,
+        }
+    }
+
+    /// Attempts to push an element at the front of the list,
+    /// returning its [Handle]
+    ///
+    /// # Errors
+    /// If the list is full, returns back the element
+    /// inside an Err variant.
+    pub fn push_front(&mut self, val: T) -> Result<Handle, T> {
+        let index = self.alloc(val)?;
+        self.link(index, NONE, self.head);
+        Ok(self.handle(index))
+    }
+
+    /// Attempts to push an element at the back of the list,
+    /// returning its [Handle]
+    ///
+    /// # Errors
+    /// If the list is full, returns back the element
+    /// inside an Err variant.
+    pub fn push_back(&mut self, val: T) -> Result<Handle, T> {
+        let index = self.alloc(val)?;
+        self.link(index, self.tail, NONE);
+        Ok(self.handle(index))
+    }
+
+    /// Attempts to insert an element right after the one
+    /// pointed by the handle, returning its [Handle]
+    ///
+    /// # Errors
+    /// If the handle is stale, or the list is full, returns
+    /// back the element inside an Err variant.
+    pub fn insert_after(&mut self, handle: Handle, val: T) -> Result<Handle, T> {
+        let Some(at) = self.index_of(handle) else {
+            return Err(val);
+        };
+        let index = self.alloc(val)?;
+        self.link(index, at, self.nodes[at].next);
+        Ok(self.handle(index))
+    }
+
+    /// Attempts to insert an element right before the one
+    /// pointed by the handle, returning its [Handle]
+    ///
+    /// # Errors
+    /// If the handle is stale, or the list is full, returns
+    /// back the element inside an Err variant.
+    pub fn insert_before(&mut self, handle: Handle, val: T) -> Result<Handle, T> {
+        let Some(at) = self.index_of(handle) else {
+            return Err(val);
+        };
+        let index = self.alloc(val)?;
+        self.link(index, self.nodes[at].prev, at);
+        Ok(self.handle(index))
+    }
+
+    /// Removes the element pointed by the handle, and returns it.
+    ///
+    /// After this, the handle (and any copy of it) becomes stale.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let index = self.index_of(handle)?;
+        self.unlink(index);
+        Some(self.free(index))
+    }
+
+    /// Removes the first element of the list, and returns it
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.remove(self.front_handle()?)
+    }
+
+    /// Removes the last element of the list, and returns it
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.back_handle()?)
+    }
+
+    /// Moves the element pointed by the handle to the front of the list.
+    /// Returns false if the handle is stale.
+    pub fn move_to_front(&mut self, handle: Handle) -> bool {
+        let Some(index) = self.index_of(handle) else {
+            return false;
+        };
+        if index != self.head {
+            self.unlink(index);
+            self.link(index, NONE, self.head);
+        }
+        true
+    }
+
+    /// Moves the element pointed by the handle to the back of the list.
+    /// Returns false if the handle is stale.
+    pub fn move_to_back(&mut self, handle: Handle) -> bool {
+        let Some(index) = self.index_of(handle) else {
+            return false;
+        };
+        if index != self.tail {
+            self.unlink(index);
+            self.link(index, self.tail, NONE);
+        }
+        true
+    }
+
+    /// Returns a reference to the element pointed by
+    /// the handle, or None if the handle is stale
+    #[inline]
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes[self.index_of(handle)?].val.as_ref()
+    }
+
+    /// Returns a mutable reference to the element pointed
+    /// by the handle, or None if the handle is stale
+    #[inline]
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        let index = self.index_of(handle)?;
+        self.nodes[index].val.as_mut()
+    }
+
+    /// Returns true if the handle points to an element of this list
+    #[inline]
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.index_of(handle).is_some()
+    }
+
+    /// Returns the handle of the element that comes after the
+    /// one pointed by the given handle, if there's any
+    pub fn next(&self, handle: Handle) -> Option<Handle> {
+        let next = self.nodes[self.index_of(handle)?].next;
+        (next != NONE).then(|| self.handle(next))
+    }
+
+    /// Returns the handle of the element that comes before
+    /// the one pointed by the given handle, if there's any
+    pub fn prev(&self, handle: Handle) -> Option<Handle> {
+        let prev = self.nodes[self.index_of(handle)?].prev;
+        (prev != NONE).then(|| self.handle(prev))
+    }
+
+    /// Returns the handle of the first element of the list
+    #[inline]
+    pub fn front_handle(&self) -> Option<Handle> {
+        (self.head != NONE).then(|| self.handle(self.head))
+    }
+
+    /// Returns the handle of the last element of the list
+    #[inline]
+    pub fn back_handle(&self) -> Option<Handle> {
+        (self.tail != NONE).then(|| self.handle(self.tail))
+    }
+
+    /// Returns a reference to the first element of the list
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(self.front_handle()?)
+    }
+
+    /// Returns a reference to the last element of the list
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.back_handle()?)
+    }
+
+    /// Removes all the elements of this list.
+    /// All the handles given out until now become stale.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Returns an iterator over the handles and elements
+    /// of this list, from front to back
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: &self.nodes,
+            front: self.head,
+            back: self.tail,
+            left: self.length,
+        }
+    }
+
+    /// Returns the capacity of this StackLinkedList
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of elements in the list
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns true if the list has no elements
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns true if no more elements can be pushed into the list
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.length == CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for StackLinkedList<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a StackLinkedList<T, CAP> {
+    type Item = (Handle, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the handles and elements of a [StackLinkedList]
+pub struct Iter<'a, T> {
+    nodes: &'a [Node<T>],
+    front: usize,
+    back: usize,
+    left: usize,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn item(&self, index: usize) -> (Handle, &'a T) {
+        let node = &self.nodes[index];
+        let handle = Handle {
+            index,
+            generation: node.generation,
+        };
+        (handle, node.val.as_ref().unwrap())
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left == 0 {
+            return None;
+        }
+        let item = self.item(self.front);
+        self.front = self.nodes[self.front].next;
+        self.left -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.left == 0 {
+            return None;
+        }
+        let item = self.item(self.back);
+        self.back = self.nodes[self.back].prev;
+        self.left -= 1;
+        Some(item)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
@@ -0,0 +1,186 @@
+use core::borrow::Borrow;
+
+use crate::StackVec;
+
+/// A fixed-capacity cache that evicts the least recently used entry.
+///
+/// Entries are kept in a [StackVec], ordered from least to most
+/// recently used. Lookups are linear, and promoting an entry
+/// shifts the ones that were used after it, so this is meant for
+/// small capacities.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackLruCache;
+///
+/// let mut cache = StackLruCache::<u32, &str, 2>::new();
+///
+/// cache.put(1, "one");
+/// cache.put(2, "two");
+///
+/// /* 1 is now the most recently used entry */
+/// assert_eq!(cache.get(&1), Some(&"one"));
+///
+/// /* So 2 gets evicted */
+/// assert_eq!(cache.put(3, "three"), Some((2, "two")));
+/// assert!(!cache.contains(&2));
+/// ```
+pub struct StackLruCache<K, V, const CAP: usize> {
+    entries: StackVec<(K, V), CAP>,
+}
+
+impl<K, V, const CAP: usize> StackLruCache<K, V, CAP> {
+    /// Creates a new empty StackLruCache
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            entries: StackVec::new(),
+        }
+    }
+
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.entries.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Moves the ith entry to the most recently used position,
+    /// and returns its new index
+    fn promote(&mut self, i: usize) -> usize {
+        self.entries[i..].rotate_left(1);
+        self.entries.len() - 1
+    }
+
+    /// Returns a reference to the value associated with the
+    /// given key, marking it as the most recently used.
+    #[inline]
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    /// Returns a mutable reference to the value associated
+    /// with the given key, marking it as the most recently used.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let i = self.position(key)?;
+        let i = self.promote(i);
+        Some(&mut self.entries[i].1)
+    }
+
+    /// Returns a reference to the value associated with
+    /// the given key, without marking it as used.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    /// Returns true if the cache contains the given key.
+    /// This doesn't mark the entry as used.
+    #[inline]
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the cache, as
+    /// the most recently used entry.
+    ///
+    /// Returns the entry that was pushed out of the cache, if any.
+    /// This is the old entry for the same key if it was already
+    /// present, or the least recently used one if the cache was full.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)>
+    where
+        K: Eq,
+    {
+        if let Some(i) = self.position(&key) {
+            let i = self.promote(i);
+            return Some(core::mem::replace(&mut self.entries[i], (key, value)));
+        }
+
+        let evicted = if self.entries.is_full() {
+            self.pop_lru()
+        } else {
+            None
+        };
+
+        match self.entries.try_push((key, value)) {
+            Ok(()) => evicted,
+            /* Only happens if CAP is 0 */
+            Err(entry) => Some(entry),
+        }
+    }
+
+    /// Removes the given key from the cache, returning its value
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let i = self.position(key)?;
+        self.entries.try_remove(i).map(|(_, v)| v)
+    }
+
+    /// Removes the least recently used entry, and returns it
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        self.entries.try_remove(0)
+    }
+
+    /// Removes all the entries from the cache
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns an iterator over the entries, from the
+    /// least recently used to the most recently used.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Returns the capacity of this StackLruCache
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of entries in the cache
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the cache has no entries
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns true if inserting a new key would evict an entry
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+}
+
+impl<K, V, const CAP: usize> Default for StackLruCache<K, V, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
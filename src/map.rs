@@ -0,0 +1,368 @@
+use core::borrow::Borrow;
+
+use crate::{CapacityError, StackVec};
+
+/// A map with a fixed capacity, backed by a [StackVec] of key-value pairs.
+///
+/// Lookups are linear, which for small capacities is usually
+/// faster than hashing.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackMap;
+///
+/// let mut map = StackMap::<&str, i32, 4>::new();
+///
+/// map.insert("a", 1).unwrap();
+/// map.insert("b", 2).unwrap();
+///
+/// assert_eq!(map.get("a"), Some(&1));
+/// assert_eq!(map.insert("a", 3), Ok(Some(1)));
+/// assert_eq!(map.remove("b"), Some(2));
+/// assert_eq!(map.len(), 1);
+/// ```
+pub struct StackMap<K, V, const CAP: usize> {
+    entries: StackVec<(K, V), CAP>,
+}
+
+impl<K, V, const CAP: usize> StackMap<K, V, CAP> {
+    /// Creates a new empty StackMap
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            entries: StackVec::new(),
+        }
+    }
+
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.entries.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Returns a reference to the value associated with the given key
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value associated with the given key
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|i| &mut self.entries[i].1)
+    }
+
+    /// Returns true if the map contains the given key
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the key was already present, its value is replaced,
+    /// and the old value is returned.
+    ///
+    /// # Errors
+    /// If the key is not present and the map is full, returns
+    /// back the key-value pair inside an Err variant.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)>
+    where
+        K: Eq,
+    {
+        match self.position(&key) {
+            Some(i) => Ok(Some(core::mem::replace(&mut self.entries[i].1, value))),
+            None => self.entries.try_push((key, value)).map(|_| None),
+        }
+    }
+
+    /// Removes the given key from the map, returning its value
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let i = self.position(key)?;
+        self.entries.try_remove(i).map(|(_, v)| v)
+    }
+
+    /// Gets the [Entry] for the given key, for in-place manipulation
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackMap;
+    ///
+    /// let mut counts = StackMap::<char, u32, 8>::new();
+    ///
+    /// for c in "hello".chars() {
+    ///     counts.entry(c).and_modify(|n| *n += 1).or_insert(1).unwrap();
+    /// }
+    ///
+    /// assert_eq!(counts.get(&'l'), Some(&2));
+    /// assert_eq!(counts.get(&'h'), Some(&1));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, CAP>
+    where
+        K: Eq,
+    {
+        match self.position(&key) {
+            Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+
+    /// Returns an iterator over the key-value pairs of the map,
+    /// in insertion order
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Returns an iterator over the key-value pairs of the map,
+    /// with mutable references to the values
+    #[inline]
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (&K, &mut V)> + ExactSizeIterator {
+        self.entries.iter_mut().map(|(k, v)| (&*k, v))
+    }
+
+    /// Returns an iterator over the keys of the map
+    #[inline]
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Returns an iterator over the values of the map
+    #[inline]
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over mutable references to the values of the map
+    #[inline]
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+
+    /// Keeps only the entries for which the predicate returns true.
+    /// The order of the remaining entries is preserved.
+    ///
+    /// Returns the number of removed entries.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackMap;
+    ///
+    /// let mut map = StackMap::<i32, &str, 4>::new();
+    /// map.insert(1, "one").unwrap();
+    /// map.insert(2, "two").unwrap();
+    /// map.insert(3, "three").unwrap();
+    ///
+    /// assert_eq!(map.retain(|k, _| k % 2 == 1), 1);
+    /// assert!(map.keys().eq(&[1, 3]));
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.entries.retain_mut(|(k, v)| f(k, v))
+    }
+
+    /// Removes all the entries from the map
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the capacity of this StackMap
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of entries in the map
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the map has no entries
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns true if no more entries can be inserted into the map
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.entries.is_full()
+    }
+}
+
+impl<K, V, const CAP: usize> Default for StackMap<K, V, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A view into a single entry of a [StackMap]
+pub enum Entry<'a, K, V, const CAP: usize> {
+    Occupied(OccupiedEntry<'a, K, V, CAP>),
+    Vacant(VacantEntry<'a, K, V, CAP>),
+}
+
+impl<'a, K, V, const CAP: usize> Entry<'a, K, V, CAP> {
+    /// Returns a mutable reference to the entry's value,
+    /// inserting the given default if it's vacant.
+    ///
+    /// # Errors
+    /// If the entry is vacant and the map is full
+    #[inline]
+    pub fn or_insert(self, default: V) -> Result<&'a mut V, CapacityError> {
+        self.or_insert_with(|| default)
+    }
+
+    /// Returns a mutable reference to the entry's value, inserting
+    /// the result of the given function if it's vacant.
+    ///
+    /// # Errors
+    /// If the entry is vacant and the map is full. In
+    /// that case, the function is not called.
+    pub fn or_insert_with<F>(self, f: F) -> Result<&'a mut V, CapacityError>
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => {
+                if e.map.is_full() {
+                    e.map.entries.overflow("or_insert_with");
+                    Err(CapacityError)
+                } else {
+                    e.insert(f()).map_err(|_| CapacityError)
+                }
+            }
+        }
+    }
+
+    /// If the entry is occupied, calls the given function
+    /// with a mutable reference to its value.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(e) = &mut self {
+            f(e.get_mut());
+        }
+        self
+    }
+
+    /// Returns a reference to this entry's key
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+}
+
+/// An occupied [Entry]
+pub struct OccupiedEntry<'a, K, V, const CAP: usize> {
+    map: &'a mut StackMap<K, V, CAP>,
+    index: usize,
+}
+
+impl<'a, K, V, const CAP: usize> OccupiedEntry<'a, K, V, CAP> {
+    /// Returns a reference to this entry's key
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.map.entries[self.index].0
+    }
+
+    /// Returns a reference to this entry's value
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.map.entries[self.index].1
+    }
+
+    /// Returns a mutable reference to this entry's value
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.entries[self.index].1
+    }
+
+    /// Converts this entry into a mutable reference to its
+    /// value, bound to the lifetime of the map.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.entries[self.index].1
+    }
+
+    /// Replaces this entry's value, and returns the old one
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes this entry from the map, and returns its value
+    #[inline]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Removes this entry from the map, and returns its key-value pair
+    pub fn remove_entry(self) -> (K, V) {
+        unsafe {
+            /* SAFETY: An OccupiedEntry always points to a valid index */
+            self.map.entries.remove_unchecked(self.index)
+        }
+    }
+}
+
+/// A vacant [Entry]
+pub struct VacantEntry<'a, K, V, const CAP: usize> {
+    map: &'a mut StackMap<K, V, CAP>,
+    key: K,
+}
+
+impl<'a, K, V, const CAP: usize> VacantEntry<'a, K, V, CAP> {
+    /// Returns a reference to this entry's key
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the value into the map, and returns
+    /// a mutable reference to it.
+    ///
+    /// # Errors
+    /// If the map is full, returns back the key and
+    /// the value inside an Err variant.
+    pub fn insert(self, value: V) -> Result<&'a mut V, (K, V)> {
+        let entries = &mut self.map.entries;
+        entries.try_push((self.key, value))?;
+        let last = entries.len() - 1;
+        Ok(&mut entries[last].1)
+    }
+}
@@ -0,0 +1,211 @@
+//! Multi-producer multi-consumer lock-free queue
+//!
+//! # Example
+//! ```
+//! use stack_vector::mpmc::Queue;
+//!
+//! let queue = Queue::<u32, 8>::new();
+//!
+//! let sum = std::thread::scope(|s| {
+//!     for t in 0..2 {
+//!         let queue = &queue;
+//!         s.spawn(move || {
+//!             for i in 1..=50 {
+//!                 while queue.enqueue(t * 100 + i).is_err() {}
+//!             }
+//!         });
+//!     }
+//!
+//!     let workers = (0..2).map(|_| {
+//!         s.spawn(|| {
+//!             let mut sum = 0;
+//!             for _ in 0..50 {
+//!                 loop {
+//!                     if let Some(n) = queue.dequeue() {
+//!                         sum += n;
+//!                         break;
+//!                     }
+//!                 }
+//!             }
+//!             sum
+//!         })
+//!     });
+//!     workers.map(|w| w.join().unwrap()).sum::<u32>()
+//! });
+//!
+//! assert_eq!(sum, 2 * 1275 + 100 * 50);
+//! ```
+
+use core::cell::UnsafeCell;
+use core::mem::{self, MaybeUninit};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+struct Slot<T> {
+    /* Tells which operation the slot is waiting for. For the
+     * nth position that maps to this slot, it's n while it's
+     * free, and n + 1 while it holds an element */
+    seq: AtomicUsize,
+    val: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// A fixed-capacity multi-producer multi-consumer queue.
+///
+/// Unlike the [SPSC queue](crate::spsc::Queue), elements can be
+/// enqueued and dequeued through a shared reference, from any
+/// number of execution contexts at the same time.
+///
+/// Each slot carries a sequence number, so producers and consumers
+/// only contend on the position counters, and never on the elements.
+pub struct Queue<T, const CAP: usize> {
+    buf: [Slot<T>; CAP],
+    enqueue_pos: AtomicUsize,
+    dequeue_pos: AtomicUsize,
+}
+
+/* SAFETY: Each slot is claimed by a single producer or consumer
+ * at a time, through the position counters and the sequence numbers */
+unsafe impl<T: Send, const CAP: usize> Sync for Queue<T, CAP> {}
+
+impl<T, const CAP: usize> Queue<T, CAP> {
+    /// Creates a new empty Queue
+    pub const fn new() -> Self {
+        let mut buf = [const {
+            Slot {
+                seq: AtomicUsize::new(0),
+                val: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }; CAP];
+        let mut i = 0;
+        while i < CAP {
+            buf[i].seq = AtomicUsize::new(i);
+            i += 1;
+        }
+        Self {
+            buf,
+            enqueue_pos: AtomicUsize::new(0),
+            dequeue_pos: AtomicUsize::new(0),
+        }
+    }
+
+    /// Attempts to push an element at the back of the queue.
+    ///
+    /// # Errors
+    /// If the queue is full, returns back the element
+    /// inside an Err variant.
+    pub fn enqueue(&self, val: T) -> Result<(), T> {
+        if CAP == 0 {
+            return Err(val);
+        }
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.buf[pos % CAP];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos) as isize;
+            if diff == 0 {
+                match self.enqueue_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe {
+                            /* SAFETY: We've claimed this position, and
+                             * consumers won't read the slot until we
+                             * update its sequence number */
+                            (*slot.val.get()).write(val);
+                        }
+                        slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(p) => pos = p,
+                }
+            } else if diff < 0 {
+                /* The slot still holds the element from the previous lap */
+                return Err(val);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Removes the element at the front of the queue, and returns it
+    pub fn dequeue(&self) -> Option<T> {
+        if CAP == 0 {
+            return None;
+        }
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.buf[pos % CAP];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq.wrapping_sub(pos.wrapping_add(1)) as isize;
+            if diff == 0 {
+                match self.dequeue_pos.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let val = unsafe {
+                            /* SAFETY: The slot is initialized, and producers
+                             * won't write to it until we update its sequence number */
+                            (*slot.val.get()).assume_init_read()
+                        };
+                        slot.seq.store(pos.wrapping_add(CAP), Ordering::Release);
+                        return Some(val);
+                    }
+                    Err(p) => pos = p,
+                }
+            } else if diff < 0 {
+                /* The slot hasn't been written yet */
+                return None;
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// If other contexts are operating on the queue
+    /// concurrently, this is only an approximation.
+    pub fn len(&self) -> usize {
+        let head = self.dequeue_pos.load(Ordering::Acquire);
+        let tail = self.enqueue_pos.load(Ordering::Acquire);
+        (tail.wrapping_sub(head) as isize).clamp(0, CAP as isize) as usize
+    }
+
+    /// Returns true if the queue has no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if no more elements can be pushed into the queue
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == CAP
+    }
+
+    /// Returns the capacity of this Queue
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for Queue<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Drop for Queue<T, CAP> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            while self.dequeue().is_some() {}
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::StackVec;
+
+/// A [StackVec] that always holds at least one element.
+///
+/// Since it can never be empty, [first](Self::first) and [last](Self::last)
+/// don't return an Option, and [pop](Self::pop) refuses to remove the last
+/// element. This moves the "one or more" check to the construction of the
+/// vector, instead of every place where it's used.
+///
+/// # Example
+/// ```
+/// use stack_vector::NonEmptyStackVec;
+///
+/// let mut samples = NonEmptyStackVec::<i32, 4>::new(3);
+/// samples.push(7);
+///
+/// assert_eq!(*samples.first(), 3);
+/// assert_eq!(*samples.last(), 7);
+///
+/// assert_eq!(samples.pop(), Some(7));
+/// /* The last element can't be popped */
+/// assert_eq!(samples.pop(), None);
+/// assert_eq!(samples.len(), 1);
+/// ```
+pub struct NonEmptyStackVec<T, const CAP: usize> {
+    inner: StackVec<T, CAP>,
+}
+
+impl<T, const CAP: usize> NonEmptyStackVec<T, CAP> {
+    /// Creates a new NonEmptyStackVec with the given element
+    ///
+    /// This method only compiles when CAP is greater than 0
+    #[inline]
+    pub fn new(first: T) -> Self {
+        const {
+            assert!(
+                CAP > 0,
+                "A NonEmptyStackVec needs a capacity greater than 0"
+            )
+        }
+        let mut inner = StackVec::new();
+        /* SAFETY: CAP is greater than 0 */
+        unsafe { inner.push_unchecked(first) };
+        Self { inner }
+    }
+
+    /// Creates a NonEmptyStackVec from a StackVec.
+    /// Returns None if the StackVec is empty.
+    #[inline]
+    pub fn from_stack_vec(sv: StackVec<T, CAP>) -> Option<Self> {
+        (!sv.is_empty()).then_some(Self { inner: sv })
+    }
+
+    /// Returns a reference to the first element
+    #[inline]
+    pub fn first(&self) -> &T {
+        &self.inner[0]
+    }
+
+    /// Returns a mutable reference to the first element
+    #[inline]
+    pub fn first_mut(&mut self) -> &mut T {
+        &mut self.inner[0]
+    }
+
+    /// Returns a reference to the last element
+    #[inline]
+    pub fn last(&self) -> &T {
+        &self.inner[self.inner.len() - 1]
+    }
+
+    /// Returns a mutable reference to the last element
+    #[inline]
+    pub fn last_mut(&mut self) -> &mut T {
+        let last = self.inner.len() - 1;
+        &mut self.inner[last]
+    }
+
+    /// Pushes an element, panicking if there is no space left.
+    ///
+    /// # Panics
+    /// - If the NonEmptyStackVec is full
+    #[inline]
+    pub fn push(&mut self, val: T) {
+        self.inner.push(val);
+    }
+
+    /// Attempts to push an element.
+    ///
+    /// # Errors
+    /// If the NonEmptyStackVec is full, returns back
+    /// the element inside an Err variant.
+    #[inline]
+    pub fn try_push(&mut self, val: T) -> Result<(), T> {
+        self.inner.try_push(val)
+    }
+
+    /// Removes the last element and returns it, unless
+    /// it's the only one. In that case, returns None.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.inner.len() > 1 {
+            self.inner.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Shortens the vector to the given length, which is clamped
+    /// to 1. If `len` is greater than the current length, this
+    /// has no effect.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.inner.len() {
+            self.inner.truncate_to(len.max(1));
+        }
+    }
+
+    /// Returns the elements as a slice
+    #[inline]
+    pub const fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+
+    /// Returns the elements as a mutable slice
+    #[inline]
+    pub const fn as_slice_mut(&mut self) -> &mut [T] {
+        self.inner.as_slice_mut()
+    }
+
+    /// Returns a reference to the underlying StackVec
+    #[inline]
+    pub const fn as_stack_vec(&self) -> &StackVec<T, CAP> {
+        &self.inner
+    }
+
+    /// Converts this NonEmptyStackVec into a regular StackVec
+    #[inline]
+    pub fn into_stack_vec(self) -> StackVec<T, CAP> {
+        self.inner
+    }
+
+    /// Returns the capacity of this NonEmptyStackVec
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns true if no more elements can be pushed
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.inner.is_full()
+    }
+}
+
+impl<T, const CAP: usize> Deref for NonEmptyStackVec<T, CAP> {
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const CAP: usize> DerefMut for NonEmptyStackVec<T, CAP> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_slice_mut()
+    }
+}
+
+impl<T, const CAP: usize> TryFrom<StackVec<T, CAP>> for NonEmptyStackVec<T, CAP> {
+    type Error = StackVec<T, CAP>;
+
+    /// Fails (returning back the StackVec) if it's empty
+    #[inline]
+    fn try_from(sv: StackVec<T, CAP>) -> Result<Self, Self::Error> {
+        if sv.is_empty() {
+            Err(sv)
+        } else {
+            Ok(Self { inner: sv })
+        }
+    }
+}
+
+impl<T, const CAP: usize> From<NonEmptyStackVec<T, CAP>> for StackVec<T, CAP> {
+    #[inline]
+    fn from(ne: NonEmptyStackVec<T, CAP>) -> Self {
+        ne.into_stack_vec()
+    }
+}
+
+impl<T: Clone, const CAP: usize> Clone for NonEmptyStackVec<T, CAP> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a NonEmptyStackVec<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,216 @@
+//! A network packet buffer with headroom and tailroom
+//!
+//! See [StackPacketBuf]
+
+use core::fmt;
+
+use crate::CapacityError;
+use crate::bytes::UnderrunError;
+
+/// A buffer of CAP bytes, holding a packet somewhere in the middle.
+///
+/// The free space before the packet (the headroom) allows prepending
+/// headers without moving the payload, as each layer of a network stack
+/// wraps the data of the one above. The free space after it (the
+/// tailroom) is used to append data, like trailers or checksums.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackPacketBuf;
+///
+/// let mut pkt = StackPacketBuf::<64>::with_headroom(16);
+/// pkt.push_back_slice(b"payload").unwrap();
+///
+/// /* Each layer prepends its header */
+/// pkt.push_front_slice(&[0x11, 0x22]).unwrap();
+/// pkt.push_front_slice(&[0xAA]).unwrap();
+///
+/// assert_eq!(pkt.as_slice(), b"\xAA\x11\x22payload");
+/// assert_eq!(pkt.headroom(), 13);
+///
+/// /* On reception, each layer strips its header */
+/// assert_eq!(pkt.strip_front(1), Ok(&[0xAA][..]));
+/// assert_eq!(pkt.strip_front(2), Ok(&[0x11, 0x22][..]));
+/// assert_eq!(pkt.as_slice(), b"payload");
+/// ```
+pub struct StackPacketBuf<const CAP: usize> {
+    buf: [u8; CAP],
+    /* The packet is buf[start..end] */
+    start: usize,
+    end: usize,
+}
+
+impl<const CAP: usize> StackPacketBuf<CAP> {
+    /// Creates a new empty StackPacketBuf, with no headroom
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_headroom(0)
+    }
+
+    /// Creates a new empty StackPacketBuf, reserving
+    /// `headroom` bytes to prepend headers.
+    ///
+    /// # Panics
+    /// - If `headroom` is greater than CAP
+    #[inline]
+    pub const fn with_headroom(headroom: usize) -> Self {
+        assert!(
+            headroom <= CAP,
+            "Headroom exceeds the capacity of the StackPacketBuf"
+        );
+        Self {
+            buf: [0; CAP],
+            start: headroom,
+            end: headroom,
+        }
+    }
+
+    /// Empties the buffer, reserving `headroom` bytes to prepend headers.
+    ///
+    /// # Panics
+    /// - If `headroom` is greater than CAP
+    #[inline]
+    pub fn reset(&mut self, headroom: usize) {
+        assert!(
+            headroom <= CAP,
+            "Headroom exceeds the capacity of the StackPacketBuf"
+        );
+        self.start = headroom;
+        self.end = headroom;
+    }
+
+    /// Returns the packet as a slice
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[self.start..self.end]
+    }
+
+    /// Returns the packet as a mutable slice
+    #[inline]
+    pub fn as_slice_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[self.start..self.end]
+    }
+
+    /// Prepends the given bytes to the packet, using the headroom.
+    ///
+    /// # Errors
+    /// If the headroom is smaller than the slice, nothing
+    /// is prepended, and a [CapacityError] is returned.
+    pub fn push_front_slice(&mut self, bytes: &[u8]) -> Result<(), CapacityError> {
+        let start = self.start.checked_sub(bytes.len()).ok_or(CapacityError)?;
+        self.buf[start..self.start].copy_from_slice(bytes);
+        self.start = start;
+        Ok(())
+    }
+
+    /// Appends the given bytes to the packet, using the tailroom.
+    ///
+    /// # Errors
+    /// If the tailroom is smaller than the slice, nothing
+    /// is appended, and a [CapacityError] is returned.
+    pub fn push_back_slice(&mut self, bytes: &[u8]) -> Result<(), CapacityError> {
+        if bytes.len() > self.tailroom() {
+            return Err(CapacityError);
+        }
+        let end = self.end + bytes.len();
+        self.buf[self.end..end].copy_from_slice(bytes);
+        self.end = end;
+        Ok(())
+    }
+
+    /// Removes the first `n` bytes of the packet, and returns them.
+    /// The space they took becomes part of the headroom.
+    ///
+    /// # Errors
+    /// If the packet is shorter than `n` bytes
+    pub fn strip_front(&mut self, n: usize) -> Result<&[u8], UnderrunError> {
+        if n > self.len() {
+            return Err(UnderrunError);
+        }
+        let start = self.start;
+        self.start += n;
+        Ok(&self.buf[start..self.start])
+    }
+
+    /// Removes the last `n` bytes of the packet, and returns them.
+    /// The space they took becomes part of the tailroom.
+    ///
+    /// # Errors
+    /// If the packet is shorter than `n` bytes
+    pub fn strip_back(&mut self, n: usize) -> Result<&[u8], UnderrunError> {
+        if n > self.len() {
+            return Err(UnderrunError);
+        }
+        let end = self.end;
+        self.end -= n;
+        Ok(&self.buf[self.end..end])
+    }
+
+    /// Returns how many bytes can be prepended
+    #[inline(always)]
+    pub const fn headroom(&self) -> usize {
+        self.start
+    }
+
+    /// Returns how many bytes can be appended
+    #[inline(always)]
+    pub const fn tailroom(&self) -> usize {
+        CAP - self.end
+    }
+
+    /// Returns the capacity of this StackPacketBuf
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the length of the packet
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the packet is empty
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl<const CAP: usize> Default for StackPacketBuf<CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> Clone for StackPacketBuf<CAP> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            buf: self.buf,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for StackPacketBuf<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackPacketBuf")
+            .field("headroom", &self.headroom())
+            .field("packet", &self.as_slice())
+            .field("tailroom", &self.tailroom())
+            .finish()
+    }
+}
+
+impl<const CAP: usize> PartialEq for StackPacketBuf<CAP> {
+    /// Compares the packets, regardless of the headroom and tailroom
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const CAP: usize> Eq for StackPacketBuf<CAP> {}
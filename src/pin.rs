@@ -0,0 +1,88 @@
+use core::pin::Pin;
+
+use crate::StackVec;
+
+/// Pinning is structural for the elements of a StackVec.
+///
+/// If a StackVec is pinned, its elements are pinned too. This is sound,
+/// because:
+/// - A StackVec is only [Unpin] if its elements are.
+/// - The elements are dropped in place, when the StackVec is dropped,
+///   so their memory is never reused before they are dropped.
+/// - The functions in this block, which receive a pinned StackVec,
+///   never move its elements.
+///
+/// This allows to store futures or intrusive timers inside a StackVec,
+/// and poll them in place.
+///
+/// # Example
+/// ```
+/// use core::pin::pin;
+/// use core::future::Future;
+/// use core::task::{Context, Poll, Waker};
+/// use stack_vector::StackVec;
+///
+/// async fn task(n: i32) -> i32 {
+///     n
+/// }
+///
+/// let mut tasks = pin!(StackVec::<_, 4>::new());
+/// assert!(tasks.as_mut().push_pinned(task(1)).is_ok());
+/// assert!(tasks.as_mut().push_pinned(task(2)).is_ok());
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let mut sum = 0;
+/// for task in tasks.as_mut().iter_pin_mut() {
+///     if let Poll::Ready(n) = task.poll(&mut cx) {
+///         sum += n;
+///     }
+/// }
+/// assert_eq!(sum, 3);
+/// ```
+impl<T, const CAP: usize> StackVec<T, CAP> {
+    /// Returns a pinned mutable reference to the ith element,
+    /// or None if the index is out of bounds.
+    #[inline]
+    pub fn get_pin_mut(self: Pin<&mut Self>, i: usize) -> Option<Pin<&mut T>> {
+        /* SAFETY: Pinning is structural for the elements,
+         * and we don't move anything */
+        unsafe {
+            self.get_unchecked_mut()
+                .get_mut(i)
+                .map(|elem| Pin::new_unchecked(elem))
+        }
+    }
+
+    /// Returns an iterator over pinned mutable references to the elements
+    #[inline]
+    pub fn iter_pin_mut(
+        self: Pin<&mut Self>,
+    ) -> impl DoubleEndedIterator<Item = Pin<&mut T>> + ExactSizeIterator {
+        /* SAFETY: Same as get_pin_mut */
+        unsafe {
+            self.get_unchecked_mut()
+                .iter_mut()
+                .map(|elem| Pin::new_unchecked(elem))
+        }
+    }
+
+    /// Pushes an element into a pinned StackVec.
+    ///
+    /// This doesn't move the elements that are already in the StackVec.
+    ///
+    /// # Errors
+    /// If the StackVec is full, returns back the element
+    /// inside an Err variant.
+    #[inline]
+    pub fn push_pinned(self: Pin<&mut Self>, val: T) -> Result<(), T> {
+        /* SAFETY: Pushing doesn't move the other elements */
+        unsafe { self.get_unchecked_mut().try_push(val) }
+    }
+
+    /// Drops all the elements of a pinned StackVec, in place
+    #[inline]
+    pub fn clear_pinned(self: Pin<&mut Self>) {
+        /* SAFETY: The elements are dropped in place, without moving them */
+        unsafe { self.get_unchecked_mut().clear() }
+    }
+}
@@ -0,0 +1,162 @@
+use core::cell::{Cell, UnsafeCell};
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+
+const NONE: usize = usize::MAX;
+
+/// A pool of CAP objects of type T.
+///
+/// Objects are allocated from the pool with [alloc](Self::alloc),
+/// which returns a [PoolGuard]. When the guard is dropped, its
+/// object is dropped too, and the slot is recycled.
+///
+/// Allocation and deallocation are O(1), since free slots are
+/// tracked with a free-list.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackPool;
+///
+/// let pool = StackPool::<[u8; 16], 2>::new();
+///
+/// let mut a = pool.alloc([0; 16]).unwrap();
+/// let b = pool.alloc([1; 16]).unwrap();
+/// a[0] = 12;
+///
+/// assert!(pool.alloc([2; 16]).is_err());
+///
+/// drop(b);
+/// assert_eq!(pool.available(), 1);
+/// assert!(pool.alloc([2; 16]).is_ok());
+/// ```
+pub struct StackPool<T, const CAP: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; CAP],
+    next_free: [Cell<usize>; CAP],
+    free_head: Cell<usize>,
+    /* Slots in [used, CAP) have never been allocated */
+    used: Cell<usize>,
+    in_use: Cell<usize>,
+}
+
+impl<T, const CAP: usize> StackPool<T, CAP> {
+    /// Creates a new StackPool, with all its slots free
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; CAP],
+            next_free: [const { Cell::new(NONE) }; CAP],
+            free_head: Cell::new(NONE),
+            used: Cell::new(0),
+            in_use: Cell::new(0),
+        }
+    }
+
+    /// Attempts to allocate an object from the pool, initialized to `value`
+    ///
+    /// # Errors
+    /// If all the slots are in use, returns back the
+    /// value inside an Err variant.
+    pub fn alloc(&self, value: T) -> Result<PoolGuard<'_, T, CAP>, T> {
+        let index = match self.free_head.get() {
+            NONE if self.used.get() < CAP => {
+                self.used.set(self.used.get() + 1);
+                self.used.get() - 1
+            }
+            NONE => return Err(value),
+            head => {
+                self.free_head.set(self.next_free[head].get());
+                head
+            }
+        };
+        self.in_use.set(self.in_use.get() + 1);
+
+        /* SAFETY: The slot was free, so no one else has a reference to it */
+        let slot = unsafe { &mut *self.slots[index].get() };
+        let value = slot.write(value);
+
+        Ok(PoolGuard {
+            pool: self,
+            index,
+            value,
+        })
+    }
+
+    fn release(&self, index: usize) {
+        self.next_free[index].set(self.free_head.get());
+        self.free_head.set(index);
+        self.in_use.set(self.in_use.get() - 1);
+    }
+
+    /// Returns the number of free slots in the pool
+    #[inline]
+    pub fn available(&self) -> usize {
+        CAP - self.in_use.get()
+    }
+
+    /// Returns the number of slots currently in use
+    #[inline]
+    pub fn in_use(&self) -> usize {
+        self.in_use.get()
+    }
+
+    /// Returns the capacity of this StackPool
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for StackPool<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An object allocated from a [StackPool].
+///
+/// When dropped, the object is dropped and its slot
+/// is returned to the pool.
+pub struct PoolGuard<'a, T, const CAP: usize> {
+    pool: &'a StackPool<T, CAP>,
+    index: usize,
+    value: &'a mut T,
+}
+
+impl<T, const CAP: usize> PoolGuard<'_, T, CAP> {
+    /// Moves the object out of the pool, releasing its slot
+    pub fn into_inner(self) -> T {
+        let guard = ManuallyDrop::new(self);
+        /* SAFETY: The guard is not dropped, so the value won't be read again */
+        let value = unsafe { (guard.value as *const T).read() };
+        guard.pool.release(guard.index);
+        value
+    }
+}
+
+impl<T, const CAP: usize> Deref for PoolGuard<'_, T, CAP> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T, const CAP: usize> DerefMut for PoolGuard<'_, T, CAP> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<T, const CAP: usize> Drop for PoolGuard<'_, T, CAP> {
+    fn drop(&mut self) {
+        unsafe {
+            /* SAFETY: The value is initialized, and since the slot is
+             * released right after, it won't be accessed again */
+            core::ptr::drop_in_place(self.value as *mut T);
+        }
+        self.pool.release(self.index);
+    }
+}
@@ -0,0 +1,47 @@
+use core::iter::FusedIterator;
+
+use crate::StackVec;
+
+/// An iterator that pops the elements from the back
+/// of a [StackVec] while they match a predicate.
+///
+/// This struct is created by [StackVec::pop_while].
+pub struct PopWhile<'a, T, F, const CAP: usize>
+where
+    F: FnMut(&T) -> bool,
+{
+    pub(super) sv: &'a mut StackVec<T, CAP>,
+    pub(super) pred: F,
+    pub(super) done: bool,
+}
+
+impl<T, F, const CAP: usize> Iterator for PopWhile<'_, T, F, CAP>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        match self.sv.last() {
+            Some(last) if (self.pred)(last) => self.sv.pop(),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.sv.len()))
+        }
+    }
+}
+
+impl<T, F, const CAP: usize> FusedIterator for PopWhile<'_, T, F, CAP> where F: FnMut(&T) -> bool {}
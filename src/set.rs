@@ -0,0 +1,262 @@
+use core::borrow::Borrow;
+use core::iter::{Chain, FusedIterator};
+use core::slice;
+
+use crate::StackVec;
+
+/// A set with a fixed capacity, backed by a [StackVec].
+///
+/// Lookups are linear, so no hashing or ordering is required
+/// on the elements, only equality.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackSet;
+///
+/// let mut a = StackSet::<i32, 4>::new();
+/// a.insert(1).unwrap();
+/// a.insert(2).unwrap();
+/// a.insert(3).unwrap();
+///
+/// let mut b = StackSet::<i32, 4>::new();
+/// b.insert(2).unwrap();
+/// b.insert(4).unwrap();
+///
+/// assert!(a.intersection(&b).eq(&[2]));
+/// assert!(a.difference(&b).eq(&[1, 3]));
+/// assert!(a.union(&b).eq(&[1, 2, 3, 4]));
+/// ```
+pub struct StackSet<T, const CAP: usize> {
+    elems: StackVec<T, CAP>,
+}
+
+impl<T, const CAP: usize> StackSet<T, CAP> {
+    /// Creates a new empty StackSet
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            elems: StackVec::new(),
+        }
+    }
+
+    fn position<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.elems.iter().position(|e| e.borrow() == value)
+    }
+
+    /// Inserts a value into the set.
+    ///
+    /// Returns true if the value wasn't already present.
+    ///
+    /// # Errors
+    /// If the value is not present and the set is full,
+    /// returns back the value inside an Err variant.
+    pub fn insert(&mut self, value: T) -> Result<bool, T>
+    where
+        T: Eq,
+    {
+        if self.contains(&value) {
+            Ok(false)
+        } else {
+            self.elems.try_push(value).map(|_| true)
+        }
+    }
+
+    /// Returns true if the set contains the given value
+    #[inline]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(value).is_some()
+    }
+
+    /// Removes a value from the set.
+    ///
+    /// Returns true if the value was present.
+    #[inline]
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Removes a value from the set, and returns it
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let i = self.position(value)?;
+        self.elems.try_remove(i)
+    }
+
+    /// Returns an iterator over the elements of this set
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.elems.iter()
+    }
+
+    /// Returns the elements of this set as a slice
+    #[inline(always)]
+    pub const fn as_slice(&self) -> &[T] {
+        self.elems.as_slice()
+    }
+
+    /// Returns an iterator over the elements that are
+    /// either in this set or in `other`, without duplicates.
+    pub fn union<'a, const OTHER: usize>(&'a self, other: &'a StackSet<T, OTHER>) -> Union<'a, T>
+    where
+        T: Eq,
+    {
+        Union {
+            iter: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    /// Returns an iterator over the elements that are
+    /// both in this set and in `other`.
+    pub fn intersection<'a, const OTHER: usize>(
+        &'a self,
+        other: &'a StackSet<T, OTHER>,
+    ) -> Intersection<'a, T>
+    where
+        T: Eq,
+    {
+        Intersection {
+            iter: self.iter(),
+            other: other.as_slice(),
+        }
+    }
+
+    /// Returns an iterator over the elements that are
+    /// in this set but not in `other`.
+    pub fn difference<'a, const OTHER: usize>(
+        &'a self,
+        other: &'a StackSet<T, OTHER>,
+    ) -> Difference<'a, T>
+    where
+        T: Eq,
+    {
+        Difference {
+            iter: self.iter(),
+            other: other.as_slice(),
+        }
+    }
+
+    /// Removes all the elements from the set
+    #[inline]
+    pub fn clear(&mut self) {
+        self.elems.clear();
+    }
+
+    /// Returns the capacity of this StackSet
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of elements in the set
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    /// Returns true if the set has no elements
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Returns true if no more elements can be inserted into the set
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.elems.is_full()
+    }
+}
+
+impl<T, const CAP: usize> Default for StackSet<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a StackSet<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator returned by [StackSet::intersection]
+pub struct Intersection<'a, T> {
+    iter: slice::Iter<'a, T>,
+    other: &'a [T],
+}
+
+impl<'a, T: Eq> Iterator for Intersection<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|e| other.contains(e))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<T: Eq> FusedIterator for Intersection<'_, T> {}
+
+/// Iterator returned by [StackSet::difference]
+pub struct Difference<'a, T> {
+    iter: slice::Iter<'a, T>,
+    other: &'a [T],
+}
+
+impl<'a, T: Eq> Iterator for Difference<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.by_ref().find(|e| !other.contains(e))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<T: Eq> FusedIterator for Difference<'_, T> {}
+
+/// Iterator returned by [StackSet::union]
+pub struct Union<'a, T> {
+    iter: Chain<slice::Iter<'a, T>, Difference<'a, T>>,
+}
+
+impl<'a, T: Eq> Iterator for Union<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: Eq> FusedIterator for Union<'_, T> {}
@@ -0,0 +1,75 @@
+//! SIMD helpers for numeric StackVecs
+//!
+//! The elements are processed in chunks of [LANES], loaded as
+//! [Simd] vectors, and the remaining ones one by one.
+
+use core::ops::{Add, Mul};
+use core::simd::prelude::*;
+
+use crate::StackVec;
+
+/// Number of elements processed at once
+const LANES: usize = 8;
+
+macro_rules! impl_simd {
+    ($add:ident, $mul:ident, $min:path, $max:path, $zero:literal => $($t:ty)*) => {
+        $(
+            impl<const CAP: usize> StackVec<$t, CAP> {
+                /// Returns the sum of all the elements, using SIMD.
+                /// Integers wrap around on overflow.
+                pub fn sum(&self) -> $t {
+                    let chunks = self.chunks_exact(LANES);
+                    let rest = chunks.remainder();
+                    let acc = chunks.fold(Simd::<$t, LANES>::splat($zero), |acc, c| {
+                        acc + Simd::from_slice(c)
+                    });
+                    rest.iter().fold(acc.reduce_sum(), |acc, &x| acc.$add(x))
+                }
+
+                /// Returns the smallest element, using SIMD.
+                /// Returns None if the StackVec is empty.
+                pub fn min(&self) -> Option<$t> {
+                    let (&first, _) = self.split_first()?;
+                    let chunks = self.chunks_exact(LANES);
+                    let rest = chunks.remainder();
+                    let acc = chunks.fold(Simd::<$t, LANES>::splat(first), |acc, c| {
+                        acc.simd_min(Simd::from_slice(c))
+                    });
+                    Some(rest.iter().fold(acc.reduce_min(), |acc, &x| $min(acc, x)))
+                }
+
+                /// Returns the greatest element, using SIMD.
+                /// Returns None if the StackVec is empty.
+                pub fn max(&self) -> Option<$t> {
+                    let (&first, _) = self.split_first()?;
+                    let chunks = self.chunks_exact(LANES);
+                    let rest = chunks.remainder();
+                    let acc = chunks.fold(Simd::<$t, LANES>::splat(first), |acc, c| {
+                        acc.simd_max(Simd::from_slice(c))
+                    });
+                    Some(rest.iter().fold(acc.reduce_max(), |acc, &x| $max(acc, x)))
+                }
+
+                /// Returns the dot product of this StackVec and the given
+                /// slice, using SIMD. Integers wrap around on overflow.
+                ///
+                /// # Panics
+                /// - If the lengths don't match
+                pub fn dot(&self, other: &[$t]) -> $t {
+                    assert_eq!(self.len(), other.len(), "Lengths don't match");
+                    let a = self.chunks_exact(LANES);
+                    let b = other.chunks_exact(LANES);
+                    let rest = a.remainder().iter().zip(b.remainder());
+                    let acc = a.zip(b).fold(Simd::<$t, LANES>::splat($zero), |acc, (a, b)| {
+                        acc + Simd::from_slice(a) * Simd::from_slice(b)
+                    });
+                    rest.fold(acc.reduce_sum(), |acc, (&x, &y)| acc.$add(x.$mul(y)))
+                }
+            }
+        )*
+    };
+}
+
+impl_simd!(add, mul, f32::min, f32::max, 0.0 => f32);
+impl_simd!(add, mul, f64::min, f64::max, 0.0 => f64);
+impl_simd!(wrapping_add, wrapping_mul, Ord::min, Ord::max, 0 => i8 i16 i32 i64 u8 u16 u32 u64);
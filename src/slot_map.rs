@@ -0,0 +1,266 @@
+use core::iter::FusedIterator;
+use core::mem;
+use core::slice;
+
+/// A handle to a value stored in a [StackSlotMap].
+///
+/// Besides the index of its slot, a key stores the generation of
+/// the slot at the time of insertion. When a value is removed, its
+/// slot's generation changes, so stale keys are detected, even if
+/// the slot is reused by another value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Key {
+    index: usize,
+    generation: u32,
+}
+
+impl Key {
+    /// Returns the index of the slot this key points to
+    #[inline(always)]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the generation of this key
+    #[inline(always)]
+    pub const fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+enum Entry<T> {
+    Occupied(T),
+    Vacant { next_free: usize },
+}
+
+struct Slot<T> {
+    generation: u32,
+    entry: Entry<T>,
+}
+
+/// A fixed-capacity container that hands out generational [keys](Key).
+///
+/// Inserting is O(1), since free slots are tracked with an
+/// intrusive free-list stored in the slots themselves.
+/// Accessing a value with a [Key] whose value was already
+/// removed returns None.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackSlotMap;
+///
+/// let mut entities = StackSlotMap::<&str, 4>::new();
+///
+/// let player = entities.insert("player").unwrap();
+/// let enemy = entities.insert("enemy").unwrap();
+///
+/// assert_eq!(entities.remove(enemy), Some("enemy"));
+///
+/// /* The slot is reused, but the old key is now stale */
+/// let bullet = entities.insert("bullet").unwrap();
+/// assert_eq!(bullet.index(), enemy.index());
+/// assert_eq!(entities.get(enemy), None);
+/// assert_eq!(entities.get(bullet), Some(&"bullet"));
+/// assert_eq!(entities.get(player), Some(&"player"));
+/// ```
+pub struct StackSlotMap<T, const CAP: usize> {
+    slots: [Slot<T>; CAP],
+    /* Head of the free-list */
+    free_head: usize,
+    /* Slots in [used, CAP) have never been occupied */
+    used: usize,
+    length: usize,
+}
+
+impl<T, const CAP: usize> StackSlotMap<T, CAP> {
+    /// Creates a new empty StackSlotMap
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            slots: [const {
+                Slot {
+                    generation: 0,
+                    entry: Entry::Vacant { next_free: NONE },
+                }
+            }; CAP],
+            free_head: NONE,
+            used: 0,
+            length: 0,
+        }
+    }
+
+    /// Attempts to insert a value into the map, returning its [Key]
+    ///
+    /// # Errors
+    /// If the map is full, returns back the value
+    /// inside an Err variant.
+    pub fn insert(&mut self, value: T) -> Result<Key, T> {
+        let index = if self.free_head != NONE {
+            let index = self.free_head;
+            let Entry::Vacant { next_free } = self.slots[index].entry else {
+                unreachable!()
+            };
+            self.free_head = next_free;
+            index
+        } else if self.used < CAP {
+            self.used += 1;
+            self.used - 1
+        } else {
+            return Err(value);
+        };
+
+        let slot = &mut self.slots[index];
+        slot.entry = Entry::Occupied(value);
+        self.length += 1;
+
+        Ok(Key {
+            index,
+            generation: slot.generation,
+        })
+    }
+
+    fn slot(&self, key: Key) -> Option<&Slot<T>> {
+        self.slots
+            .get(key.index)
+            .filter(|s| s.generation == key.generation)
+    }
+
+    /// Returns a reference to the value associated with
+    /// the key, or None if the key is stale.
+    pub fn get(&self, key: Key) -> Option<&T> {
+        match &self.slot(key)?.entry {
+            Entry::Occupied(v) => Some(v),
+            Entry::Vacant { .. } => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value associated
+    /// with the key, or None if the key is stale.
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut T> {
+        match self.slots.get_mut(key.index) {
+            Some(Slot {
+                generation,
+                entry: Entry::Occupied(v),
+            }) if *generation == key.generation => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the key points to a value in this map
+    #[inline]
+    pub fn contains_key(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes the value associated with the key, and returns it.
+    ///
+    /// After this, the key (and any copy of it) becomes stale.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let slot = &mut self.slots[key.index];
+        let entry = mem::replace(
+            &mut slot.entry,
+            Entry::Vacant {
+                next_free: self.free_head,
+            },
+        );
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free_head = key.index;
+        self.length -= 1;
+
+        match entry {
+            Entry::Occupied(v) => Some(v),
+            Entry::Vacant { .. } => unreachable!(),
+        }
+    }
+
+    /// Removes all the values of this map.
+    /// All the keys handed out until now become stale.
+    pub fn clear(&mut self) {
+        for index in 0..self.used {
+            let key = Key {
+                index,
+                generation: self.slots[index].generation,
+            };
+            self.remove(key);
+        }
+    }
+
+    /// Returns an iterator over the keys and values of this map
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: self.slots[..self.used].iter().enumerate(),
+            left: self.length,
+        }
+    }
+
+    /// Returns the capacity of this StackSlotMap
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns the number of values in the map
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns true if the map has no values
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns true if no more values can be inserted into the map
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.length == CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for StackSlotMap<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the keys and values of a [StackSlotMap]
+pub struct Iter<'a, T> {
+    slots: core::iter::Enumerate<slice::Iter<'a, Slot<T>>>,
+    left: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Key, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.slots.find_map(|(index, slot)| match &slot.entry {
+            Entry::Occupied(v) => Some((
+                Key {
+                    index,
+                    generation: slot.generation,
+                },
+                v,
+            )),
+            Entry::Vacant { .. } => None,
+        })?;
+        self.left -= 1;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
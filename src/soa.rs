@@ -0,0 +1,194 @@
+/// Declares a struct-of-arrays container, backed by one
+/// [StackVec](crate::StackVec) per field.
+///
+/// Each field is stored in its own StackVec, all of them with the
+/// same length, so loops that only touch a few fields go over
+/// contiguous memory. Elements are pushed, removed and iterated
+/// as tuples, with the fields in the order they were declared.
+///
+/// The generated struct has these methods:
+/// - `new`, `push`, `pop`, `remove`, `get`, `clear`
+/// - `iter` and `iter_mut`, which yield tuples of references
+/// - A method for each field, returning its elements as a slice
+/// - `columns_mut`, which returns a tuple with every field as a mutable slice
+/// - `len`, `is_empty`, `is_full` and `capacity`
+///
+/// Since each field gets a method with its name, the fields can't be
+/// named like any of the methods above. Those names are reserved, and
+/// using them causes a duplicate definition error.
+///
+/// # Example
+/// ```
+/// stack_vector::stack_soa! {
+///     /// Particles of a simulation
+///     pub struct Particles[64] {
+///         pos: f32,
+///         vel: f32,
+///         alive: bool,
+///     }
+/// }
+///
+/// let mut particles = Particles::new();
+/// particles.push(0.0, 1.5, true).unwrap();
+/// particles.push(4.0, -0.5, true).unwrap();
+///
+/// let (pos, vel, _) = particles.columns_mut();
+/// for (p, v) in pos.iter_mut().zip(vel.iter()) {
+///     *p += v;
+/// }
+///
+/// assert_eq!(particles.pos(), &[1.5, 3.5]);
+/// assert_eq!(particles.get(1), Some((&3.5, &-0.5, &true)));
+/// assert_eq!(particles.remove(0), (1.5, 1.5, true));
+/// assert_eq!(particles.len(), 1);
+/// ```
+#[macro_export]
+macro_rules! stack_soa {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident [$cap:expr] {
+            $($field:ident : $ty:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            /* All the fields have the same length */
+            $($field: $crate::StackVec<$ty, { $cap }>,)+
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            /// Creates a new empty container
+            #[inline]
+            pub const fn new() -> Self {
+                Self {
+                    $($field: $crate::StackVec::new(),)+
+                }
+            }
+
+            /// Attempts to push an element.
+            ///
+            /// # Errors
+            /// If the container is full, returns back the
+            /// fields inside an Err variant.
+            pub fn push(&mut self, $($field: $ty),+) -> Result<(), ($($ty,)+)> {
+                /* Check every field, instead of relying on
+                 * all of them having the same length */
+                if $(self.$field.is_full())||+ {
+                    return Err(($($field,)+));
+                }
+                $(
+                    /* SAFETY: We've checked that there's room in every field */
+                    unsafe { self.$field.push_unchecked($field) };
+                )+
+                Ok(())
+            }
+
+            /// Removes the last element and returns it
+            pub fn pop(&mut self) -> Option<($($ty,)+)> {
+                if self.is_empty() {
+                    return None;
+                }
+                Some(($(self.$field.pop().unwrap(),)+))
+            }
+
+            /// Removes the ith element and returns it
+            ///
+            /// # Panics
+            /// - If the index is out of bounds
+            pub fn remove(&mut self, i: usize) -> ($($ty,)+) {
+                assert!(
+                    i < self.len(),
+                    "Index {i} out of bounds for a container of length {}",
+                    self.len()
+                );
+                ($(self.$field.remove(i),)+)
+            }
+
+            /// Returns references to the fields of the ith element
+            #[inline]
+            pub fn get(&self, i: usize) -> Option<($(&$ty,)+)> {
+                if i >= self.len() {
+                    return None;
+                }
+                Some(($(&self.$field[i],)+))
+            }
+
+            /// Removes all the elements
+            pub fn clear(&mut self) {
+                /* Each field is cleared by a guard, so if a Drop panics,
+                 * the rest of the fields are still cleared while unwinding,
+                 * and all of them end up with the same length */
+                struct ClearOnDrop<'a, T, const CAP: usize>(&'a mut $crate::StackVec<T, CAP>);
+
+                impl<T, const CAP: usize> ::core::ops::Drop for ClearOnDrop<'_, T, CAP> {
+                    fn drop(&mut self) {
+                        self.0.clear();
+                    }
+                }
+
+                $(let $field = ClearOnDrop(&mut self.$field);)+
+                $(::core::mem::drop($field);)+
+            }
+
+            /// Returns an iterator over references to the fields of each element
+            pub fn iter(&self) -> impl Iterator<Item = ($(&$ty,)+)> {
+                let ($(mut $field,)+) = ($(self.$field.iter(),)+);
+                ::core::iter::from_fn(move || Some(($($field.next()?,)+)))
+            }
+
+            /// Returns an iterator over mutable references
+            /// to the fields of each element
+            pub fn iter_mut(&mut self) -> impl Iterator<Item = ($(&mut $ty,)+)> {
+                let ($(mut $field,)+) = ($(self.$field.iter_mut(),)+);
+                ::core::iter::from_fn(move || Some(($($field.next()?,)+)))
+            }
+
+            $(
+                /// Returns the values of this field, for every element
+                #[inline]
+                pub fn $field(&self) -> &[$ty] {
+                    self.$field.as_slice()
+                }
+            )+
+
+            /// Returns the values of every field as mutable slices
+            #[inline]
+            pub fn columns_mut(&mut self) -> ($(&mut [$ty],)+) {
+                ($(self.$field.as_slice_mut(),)+)
+            }
+
+            /// Returns the number of elements
+            #[inline(always)]
+            pub const fn len(&self) -> usize {
+                /* Every field has the same length, so take the first one */
+                [$(self.$field.len(),)+][0]
+            }
+
+            /// Returns true if there are no elements
+            #[inline(always)]
+            pub const fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Returns true if no more elements can be pushed
+            #[inline(always)]
+            pub const fn is_full(&self) -> bool {
+                self.len() == $cap
+            }
+
+            /// Returns the capacity of this container
+            #[inline(always)]
+            pub const fn capacity(&self) -> usize {
+                $cap
+            }
+        }
+
+        impl ::core::default::Default for $name {
+            #[inline(always)]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
@@ -0,0 +1,494 @@
+use core::cmp::Ordering;
+use core::ptr;
+use core::slice;
+
+use crate::{CapacityError, StackVec};
+
+/// Operations on StackVecs whose elements are sorted in ascending order.
+///
+/// If the elements are not sorted, these functions don't cause
+/// undefined behaviour, but their results are unspecified.
+impl<T: Ord, const CAP: usize> StackVec<T, CAP> {
+    /// Merges the elements of the given sorted sequence into this
+    /// sorted StackVec, in linear time. Elements of this StackVec
+    /// come before equal elements of the other sequence.
+    ///
+    /// # Errors
+    /// If the elements don't fit, this StackVec is left unchanged,
+    /// and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut ids = StackVec::<u32, 8>::new();
+    /// ids.extend_from_iter([1, 4, 9]);
+    ///
+    /// ids.merge_sorted([2, 4, 10]).unwrap();
+    /// assert_eq!(ids.as_slice(), &[1, 2, 4, 4, 9, 10]);
+    ///
+    /// assert!(ids.merge_sorted([0, 0, 0]).is_err());
+    /// ```
+    pub fn merge_sorted<I>(&mut self, other: I) -> Result<(), CapacityError>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: DoubleEndedIterator + ExactSizeIterator,
+    {
+        let mut other = other.into_iter();
+        let m = other.len();
+        self.ensure_remaining(m)?;
+
+        /* We merge from the back, so the elements of this StackVec
+         * are never overwritten before being moved. While merging,
+         * [0, left) holds the elements of self not merged yet, and
+         * [out, end) the ones already merged. If the iterator panics,
+         * or yields less elements than it said, the guard closes the gap */
+        struct Guard<'a, T, const CAP: usize> {
+            sv: &'a mut StackVec<T, CAP>,
+            left: usize,
+            out: usize,
+            end: usize,
+        }
+
+        impl<T, const CAP: usize> Drop for Guard<'_, T, CAP> {
+            fn drop(&mut self) {
+                let base = self.sv.as_mut_ptr();
+                let merged = self.end - self.out;
+                unsafe {
+                    /* SAFETY: Both ranges are within the buffer */
+                    ptr::copy(base.add(self.out), base.add(self.left), merged);
+                }
+                self.sv.length = 0;
+                self.sv.grow(self.left + merged);
+                self.sv.poison(self.left + merged, self.end);
+            }
+        }
+
+        let end = self.length + m;
+        let left = self.length;
+        /* Until the merge is finished, the length only covers the
+         * elements that are guaranteed to be in place */
+        self.length = 0;
+        let mut g = Guard {
+            sv: self,
+            left,
+            out: end,
+            end,
+        };
+        let base = g.sv.as_mut_ptr();
+
+        while g.out > g.left {
+            let Some(elem) = other.next_back() else { break };
+            unsafe {
+                /* SAFETY: Elements in [0, left) are initialized, and
+                 * out > left, so the slot at out - 1 is free */
+                while g.left > 0 && elem < *base.add(g.left - 1) {
+                    g.left -= 1;
+                    g.out -= 1;
+                    ptr::copy_nonoverlapping(base.add(g.left), base.add(g.out), 1);
+                }
+                g.out -= 1;
+                base.add(g.out).write(elem);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merges this sorted StackVec with the given sorted slice, pushing
+    /// the result into `out`, in linear time. Elements of this StackVec
+    /// come before equal elements of the other slice.
+    ///
+    /// # Errors
+    /// If the elements don't fit in `out`, it's left
+    /// unchanged, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let a = StackVec::from_array([1, 3, 5]);
+    /// let mut out = StackVec::<i32, 6>::new();
+    ///
+    /// a.merge_sorted_into(&[2, 4, 6], &mut out).unwrap();
+    /// assert_eq!(out.as_slice(), &[1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn merge_sorted_into<const OUT: usize>(
+        &self,
+        other: &[T],
+        out: &mut StackVec<T, OUT>,
+    ) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        out.ensure_remaining(self.len() + other.len())?;
+        let (mut a, mut b) = (self.as_slice(), other);
+        while let (Some(x), Some(y)) = (a.first(), b.first()) {
+            let elem = if y < x {
+                b = &b[1..];
+                y
+            } else {
+                a = &a[1..];
+                x
+            };
+            /* SAFETY: We've checked that all the elements fit */
+            unsafe { out.push_unchecked(elem.clone()) };
+        }
+        for elem in a.iter().chain(b) {
+            /* SAFETY: Same as above */
+            unsafe { out.push_unchecked(elem.clone()) };
+        }
+        Ok(())
+    }
+}
+
+/// Runs a linear merge of two sorted and deduplicated slices, pushing
+/// clones of the elements selected by `keep` into `out`. `keep` receives
+/// [Ordering::Less] for elements only on the first slice, [Ordering::Greater]
+/// for elements only on the second one, and [Ordering::Equal] for elements
+/// on both. On overflow, `out` is left unchanged.
+fn merge_into<T, const OUT: usize>(
+    mut a: &[T],
+    mut b: &[T],
+    out: &mut StackVec<T, OUT>,
+    keep: impl Fn(Ordering) -> bool,
+) -> Result<(), CapacityError>
+where
+    T: Ord + Clone,
+{
+    let len = out.len();
+    let mut push = |ord: Ordering, elem: &T| {
+        if keep(ord) && out.try_push(elem.clone()).is_err() {
+            return Err(CapacityError);
+        }
+        Ok(())
+    };
+    let res = (|| {
+        while let (Some(x), Some(y)) = (a.first(), b.first()) {
+            let ord = x.cmp(y);
+            match ord {
+                Ordering::Less => a = &a[1..],
+                Ordering::Greater => b = &b[1..],
+                Ordering::Equal => {
+                    a = &a[1..];
+                    b = &b[1..];
+                }
+            }
+            push(ord, if ord == Ordering::Greater { y } else { x })?;
+        }
+        a.iter().try_for_each(|x| push(Ordering::Less, x))?;
+        b.iter().try_for_each(|y| push(Ordering::Greater, y))
+    })();
+    if res.is_err() {
+        out.truncate_to(len);
+    }
+    res
+}
+
+/// Set operations on StackVecs whose elements are sorted in ascending
+/// order and deduplicated, like the ones of a set.
+impl<T: Ord + Clone, const CAP: usize> StackVec<T, CAP> {
+    /// Pushes into `out` the elements that are both in this StackVec
+    /// and in the given slice, in linear time.
+    ///
+    /// # Errors
+    /// If the elements don't fit in `out`, it's left
+    /// unchanged, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let a = StackVec::from_array([1, 2, 3, 5]);
+    /// let mut out = StackVec::<i32, 4>::new();
+    ///
+    /// a.intersection_with(&[2, 4, 5], &mut out).unwrap();
+    /// assert_eq!(out.as_slice(), &[2, 5]);
+    /// ```
+    pub fn intersection_with<const OUT: usize>(
+        &self,
+        other: &[T],
+        out: &mut StackVec<T, OUT>,
+    ) -> Result<(), CapacityError> {
+        merge_into(self, other, out, |ord| ord == Ordering::Equal)
+    }
+
+    /// Pushes into `out` the elements that are in this StackVec,
+    /// in the given slice, or in both, in linear time.
+    ///
+    /// # Errors
+    /// If the elements don't fit in `out`, it's left
+    /// unchanged, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let a = StackVec::from_array([1, 2, 5]);
+    /// let mut out = StackVec::<i32, 8>::new();
+    ///
+    /// a.union_into(&[2, 4], &mut out).unwrap();
+    /// assert_eq!(out.as_slice(), &[1, 2, 4, 5]);
+    /// ```
+    pub fn union_into<const OUT: usize>(
+        &self,
+        other: &[T],
+        out: &mut StackVec<T, OUT>,
+    ) -> Result<(), CapacityError> {
+        merge_into(self, other, out, |_| true)
+    }
+
+    /// Pushes into `out` the elements that are in this StackVec,
+    /// but not in the given slice, in linear time.
+    ///
+    /// # Errors
+    /// If the elements don't fit in `out`, it's left
+    /// unchanged, and a [CapacityError] is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let a = StackVec::from_array([1, 2, 3, 5]);
+    /// let mut out = StackVec::<i32, 4>::new();
+    ///
+    /// a.difference_into(&[2, 4, 5], &mut out).unwrap();
+    /// assert_eq!(out.as_slice(), &[1, 3]);
+    /// ```
+    pub fn difference_into<const OUT: usize>(
+        &self,
+        other: &[T],
+        out: &mut StackVec<T, OUT>,
+    ) -> Result<(), CapacityError> {
+        merge_into(self, other, out, |ord| ord == Ordering::Less)
+    }
+}
+
+/// Below this length, slices are sorted with insertion sort
+const INSERTION_SORT_THRESHOLD: usize = 16;
+
+/// Stable sorting, without allocations
+impl<T, const CAP: usize> StackVec<T, CAP> {
+    /// Sorts this StackVec, preserving the order of equal elements.
+    ///
+    /// This is a merge sort that doesn't need a heap. If the spare capacity
+    /// of the StackVec has room for half of the elements, it's used as the
+    /// merge buffer, making the sort O(n log n). Otherwise, the runs are
+    /// merged in place, by rotating them, which is O(n log² n).
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::<_, 8>::new();
+    /// sv.extend_from_iter([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+    ///
+    /// sv.sort_stable_by_key(|(n, _)| *n);
+    /// assert_eq!(sv.as_slice(), &[(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    /// ```
+    #[inline]
+    pub fn sort_stable(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_stable_by(T::cmp);
+    }
+
+    /// Sorts this StackVec with the given key extraction function,
+    /// preserving the order of elements with equal keys.
+    ///
+    /// See [sort_stable](Self::sort_stable)
+    #[inline]
+    pub fn sort_stable_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.sort_stable_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Sorts this StackVec with the given comparator function,
+    /// preserving the order of equal elements.
+    ///
+    /// See [sort_stable](Self::sort_stable)
+    pub fn sort_stable_by<F>(&mut self, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = self.length;
+        let base = self.as_mut_ptr();
+        /* SAFETY: Elements in [0, len) are initialized, and the spare
+         * capacity is disjoint from them */
+        let (elems, buf) = unsafe { (slice::from_raw_parts_mut(base, len), base.add(len)) };
+        merge_sort(elems, buf, CAP - len, &mut cmp);
+    }
+
+    /// Sorts this StackVec with the given key extraction function,
+    /// calling it only once per element.
+    ///
+    /// The keys are cached in a buffer on the stack, with room for
+    /// CAP keys. This is useful when the keys are expensive to compute.
+    /// The sort is stable.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackVec;
+    ///
+    /// let mut sv = StackVec::from_array(["10", "9", "100", "1"]);
+    ///
+    /// sv.sort_by_cached_key(|s| s.parse::<u32>().unwrap());
+    /// assert_eq!(sv.as_slice(), &["1", "9", "10", "100"]);
+    /// ```
+    pub fn sort_by_cached_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let mut indices = StackVec::<(K, usize), CAP>::new();
+        for (i, elem) in self.iter().enumerate() {
+            /* SAFETY: There are at most CAP elements */
+            unsafe { indices.push_unchecked((key(elem), i)) };
+        }
+        /* The indices are unique, so the unstable sort is stable */
+        indices.sort_unstable();
+
+        /* Apply the permutation. The element that should go to i may
+         * have been swapped already. In that case, follow the swaps */
+        for i in 0..self.length {
+            let mut index = indices[i].1;
+            while index < i {
+                index = indices[index].1;
+            }
+            indices[i].1 = index;
+            self.swap(i, index);
+        }
+    }
+}
+
+/// Sorts `v` with a stable merge sort. `buf` points to `buf_len`
+/// uninitialized slots, that don't overlap with `v`.
+fn merge_sort<T, F>(v: &mut [T], buf: *mut T, buf_len: usize, cmp: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = v.len();
+    if len <= INSERTION_SORT_THRESHOLD {
+        insertion_sort(v, cmp);
+        return;
+    }
+    let mid = len / 2;
+    merge_sort(&mut v[..mid], buf, buf_len, cmp);
+    merge_sort(&mut v[mid..], buf, buf_len, cmp);
+    if cmp(&v[mid], &v[mid - 1]) != Ordering::Less {
+        /* Already in order */
+        return;
+    }
+    if buf_len >= mid {
+        /* SAFETY: The buffer has room for the left run */
+        unsafe { merge_buffered(v, mid, buf, cmp) };
+    } else {
+        merge_in_place(v, mid, cmp);
+    }
+}
+
+fn insertion_sort<T, F>(v: &mut [T], cmp: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 && cmp(&v[j], &v[j - 1]) == Ordering::Less {
+            v.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// Merges the sorted runs [0, mid) and [mid, len) of `v`,
+/// moving the left one to `buf`, which must have room for it.
+unsafe fn merge_buffered<T, F>(v: &mut [T], mid: usize, buf: *mut T, cmp: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    /* The elements of the left run that are still in the buffer, in
+     * [src, end), are moved to dest when this is dropped. If cmp
+     * panics, that fills the gap left in v */
+    struct Hole<T> {
+        src: *mut T,
+        end: *mut T,
+        dest: *mut T,
+    }
+
+    impl<T> Drop for Hole<T> {
+        fn drop(&mut self) {
+            unsafe {
+                let n = self.end.offset_from(self.src) as usize;
+                ptr::copy_nonoverlapping(self.src, self.dest, n);
+            }
+        }
+    }
+
+    let len = v.len();
+    let v = v.as_mut_ptr();
+    unsafe {
+        ptr::copy_nonoverlapping(v, buf, mid);
+        let mut hole = Hole {
+            src: buf,
+            end: buf.add(mid),
+            dest: v,
+        };
+        let mut right = v.add(mid);
+        let right_end = v.add(len);
+
+        /* SAFETY: dest is always before right, since there are elements
+         * of the left run left. So no element is overwritten before being moved */
+        while hole.src < hole.end && right < right_end {
+            let from = if cmp(&*right, &*hole.src) == Ordering::Less {
+                right = right.add(1);
+                right.sub(1)
+            } else {
+                hole.src = hole.src.add(1);
+                hole.src.sub(1)
+            };
+            ptr::copy_nonoverlapping(from, hole.dest, 1);
+            hole.dest = hole.dest.add(1);
+        }
+    }
+}
+
+/// Merges the sorted runs [0, mid) and [mid, len) of `v` in place,
+/// splitting them and rotating the middle part, recursively.
+fn merge_in_place<T, F>(v: &mut [T], mid: usize, cmp: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = v.len();
+    if mid == 0 || mid == len {
+        return;
+    }
+    if len == 2 {
+        if cmp(&v[1], &v[0]) == Ordering::Less {
+            v.swap(0, 1);
+        }
+        return;
+    }
+
+    /* Split the longest run at its middle, and find where that
+     * element would go in the other run. Ties are resolved so
+     * that the elements of the left run stay first */
+    let (cut1, cut2) = if mid >= len - mid {
+        let cut1 = mid / 2;
+        let cut2 = mid + v[mid..].partition_point(|x| cmp(x, &v[cut1]) == Ordering::Less);
+        (cut1, cut2)
+    } else {
+        let cut2 = mid + (len - mid) / 2;
+        let cut1 = v[..mid].partition_point(|x| cmp(x, &v[cut2]) != Ordering::Greater);
+        (cut1, cut2)
+    };
+
+    v[cut1..cut2].rotate_left(mid - cut1);
+    let new_mid = cut1 + (cut2 - mid);
+    let (left, right) = v.split_at_mut(new_mid);
+    merge_in_place(left, cut1, cmp);
+    merge_in_place(right, cut2 - new_mid, cmp);
+}
@@ -0,0 +1,59 @@
+use core::iter::FusedIterator;
+use core::slice;
+
+use crate::StackVec;
+
+/// An iterator over the pieces of a [StackVec] between the
+/// elements that match a predicate, as owned StackVecs.
+///
+/// This struct is created by [StackVec::split_on].
+pub struct SplitOn<'a, T, F, const CAP: usize>
+where
+    F: FnMut(&T) -> bool,
+{
+    pub(super) inner: slice::Split<'a, T, F>,
+}
+
+impl<T: Clone, F, const CAP: usize> SplitOn<'_, T, F, CAP>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn to_owned(piece: &[T]) -> StackVec<T, CAP> {
+        let mut sv = StackVec::new();
+        /* The piece is part of a StackVec<T, CAP>, so it fits */
+        let _ = sv.extend_cloned(piece);
+        sv
+    }
+}
+
+impl<T: Clone, F, const CAP: usize> Iterator for SplitOn<'_, T, F, CAP>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = StackVec<T, CAP>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(Self::to_owned)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Clone, F, const CAP: usize> DoubleEndedIterator for SplitOn<'_, T, F, CAP>
+where
+    F: FnMut(&T) -> bool,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(Self::to_owned)
+    }
+}
+
+impl<T: Clone, F, const CAP: usize> FusedIterator for SplitOn<'_, T, F, CAP> where
+    F: FnMut(&T) -> bool
+{
+}
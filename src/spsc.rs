@@ -0,0 +1,315 @@
+//! Single-producer single-consumer lock-free queue
+//!
+//! # Example
+//! ```
+//! use stack_vector::spsc::Queue;
+//!
+//! let mut queue = Queue::<u8, 4>::new();
+//! let (mut tx, mut rx) = queue.split();
+//!
+//! std::thread::scope(|s| {
+//!     s.spawn(move || {
+//!         for b in b"hello" {
+//!             while tx.enqueue(*b).is_err() {}
+//!         }
+//!     });
+//!
+//!     let mut received = Vec::new();
+//!     while received.len() < 5 {
+//!         if let Some(b) = rx.dequeue() {
+//!             received.push(b);
+//!         }
+//!     }
+//!     assert_eq!(received, b"hello");
+//! });
+//! ```
+
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A fixed-capacity single-producer single-consumer queue.
+///
+/// The queue can be [split](Self::split) into a [Producer]
+/// and a [Consumer], which can be moved to different execution
+/// contexts (e.g. an interrupt handler and the main loop), and
+/// operate on the queue concurrently without locking.
+pub struct Queue<T, const CAP: usize> {
+    buf: [UnsafeCell<MaybeUninit<T>>; CAP],
+    /* head and tail run in [0, 2 * CAP), so that a full
+     * queue can be told apart from an empty one */
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+/* SAFETY: The Producer and the Consumer never access the same
+ * slot at the same time, and only one of each can exist */
+unsafe impl<T: Send, const CAP: usize> Sync for Queue<T, CAP> {}
+
+impl<T, const CAP: usize> Queue<T, CAP> {
+    /// Creates a new empty Queue
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buf: [const { UnsafeCell::new(MaybeUninit::uninit()) }; CAP],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline(always)]
+    const fn increment(i: usize) -> usize {
+        (i + 1) % (2 * CAP)
+    }
+
+    #[inline(always)]
+    const fn distance(head: usize, tail: usize) -> usize {
+        (tail + 2 * CAP - head) % (2 * CAP)
+    }
+
+    /// Splits the queue into a [Producer] and a [Consumer]
+    #[inline]
+    pub fn split(&mut self) -> (Producer<'_, T, CAP>, Consumer<'_, T, CAP>) {
+        (
+            Producer {
+                queue: self,
+                _not_sync: PhantomData,
+            },
+            Consumer {
+                queue: self,
+                _not_sync: PhantomData,
+            },
+        )
+    }
+
+    /// # Safety
+    /// Must only be called from the producer side
+    unsafe fn inner_enqueue(&self, val: T) -> Result<(), T> {
+        if CAP == 0 {
+            return Err(val);
+        }
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if Self::distance(head, tail) == CAP {
+            return Err(val);
+        }
+        unsafe {
+            /* SAFETY: The slot is free, and the consumer
+             * won't touch it until we bump the tail */
+            (*self.buf[tail % CAP].get()).write(val);
+        }
+        self.tail.store(Self::increment(tail), Ordering::Release);
+        Ok(())
+    }
+
+    /// # Safety
+    /// Must only be called from the consumer side
+    unsafe fn inner_dequeue(&self) -> Option<T> {
+        if CAP == 0 {
+            return None;
+        }
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let val = unsafe {
+            /* SAFETY: The slot is initialized, and the producer
+             * won't touch it until we bump the head */
+            (*self.buf[head % CAP].get()).assume_init_read()
+        };
+        self.head.store(Self::increment(head), Ordering::Release);
+        Some(val)
+    }
+
+    unsafe fn inner_peek(&self) -> Option<&T> {
+        if CAP == 0 {
+            return None;
+        }
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        /* SAFETY: Same as inner_dequeue */
+        Some(unsafe { (*self.buf[head % CAP].get()).assume_init_ref() })
+    }
+
+    /// Attempts to push an element at the back of the queue.
+    ///
+    /// # Errors
+    /// If the queue is full, returns back the element
+    /// inside an Err variant.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Result<(), T> {
+        /* SAFETY: We have exclusive access to the queue */
+        unsafe { self.inner_enqueue(val) }
+    }
+
+    /// Removes the element at the front of the queue, and returns it
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        /* SAFETY: We have exclusive access to the queue */
+        unsafe { self.inner_dequeue() }
+    }
+
+    /// Returns the number of elements in the queue
+    pub fn len(&self) -> usize {
+        if CAP == 0 {
+            return 0;
+        }
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        Self::distance(head, tail)
+    }
+
+    /// Returns true if the queue has no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if no more elements can be pushed into the queue
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == CAP
+    }
+
+    /// Returns the capacity of this Queue
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for Queue<T, CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Drop for Queue<T, CAP> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            while self.dequeue().is_some() {}
+        }
+    }
+}
+
+/// The producer half of a [Queue]
+pub struct Producer<'a, T, const CAP: usize> {
+    queue: &'a Queue<T, CAP>,
+    /* Only one thread at a time may use each half */
+    _not_sync: PhantomData<*const ()>,
+}
+
+/* SAFETY: The Producer only moves elements into the queue */
+unsafe impl<T: Send, const CAP: usize> Send for Producer<'_, T, CAP> {}
+
+impl<T, const CAP: usize> Producer<'_, T, CAP> {
+    /// Attempts to push an element at the back of the queue.
+    ///
+    /// # Errors
+    /// If the queue is full, returns back the element
+    /// inside an Err variant.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Result<(), T> {
+        /* SAFETY: There's only one Producer for the queue */
+        unsafe { self.queue.inner_enqueue(val) }
+    }
+
+    /// Returns true if there's room for another element
+    #[inline]
+    pub fn ready(&self) -> bool {
+        !self.queue.is_full()
+    }
+
+    /// Returns the number of elements in the queue
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if the queue has no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns the capacity of the queue
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
+
+/// The consumer half of a [Queue]
+///
+/// The Consumer can be moved to another thread, but not shared
+/// between threads, since [peek](Self::peek) hands out references
+/// to the elements, which may not be [Sync].
+///
+/// ```compile_fail
+/// use std::cell::Cell;
+/// use stack_vector::spsc::Queue;
+///
+/// let mut queue = Queue::<Cell<u32>, 4>::new();
+/// let (_, rx) = queue.split();
+/// let rx = &rx;
+///
+/// std::thread::scope(|s| {
+///     s.spawn(move || rx.peek().map(|c| c.set(1)));
+///     s.spawn(move || rx.peek().map(|c| c.set(2)));
+/// });
+/// ```
+pub struct Consumer<'a, T, const CAP: usize> {
+    queue: &'a Queue<T, CAP>,
+    /* Only one thread at a time may use each half */
+    _not_sync: PhantomData<*const ()>,
+}
+
+/* SAFETY: The Consumer only moves elements out of the queue, and
+ * the references from peek can't outlive the borrow of it */
+unsafe impl<T: Send, const CAP: usize> Send for Consumer<'_, T, CAP> {}
+
+impl<T, const CAP: usize> Consumer<'_, T, CAP> {
+    /// Removes the element at the front of the queue, and returns it
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        /* SAFETY: There's only one Consumer for the queue */
+        unsafe { self.queue.inner_dequeue() }
+    }
+
+    /// Returns a reference to the element at the front of the queue
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        /* SAFETY: There's only one Consumer for the queue */
+        unsafe { self.queue.inner_peek() }
+    }
+
+    /// Returns true if there's an element ready to be dequeued
+    #[inline]
+    pub fn ready(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    /// Returns the number of elements in the queue
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns true if the queue has no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns the capacity of the queue
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+}
@@ -0,0 +1,500 @@
+//! A [String]-like wrapper for a [StackVec] of bytes
+//!
+//! [String]: https://doc.rust-lang.org/std/string/struct.String.html
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{Deref, DerefMut, RangeBounds};
+use core::str;
+
+use crate::{CapacityError, StackVec};
+
+/// A UTF-8 encoded string with a fixed capacity of CAP bytes.
+///
+/// # Example
+/// ```
+/// use stack_vector::StackString;
+///
+/// let mut s = StackString::<16>::new();
+/// s.push_str("Hello");
+/// s.push(',');
+/// s.push_str(" world");
+///
+/// assert_eq!(s.as_str(), "Hello, world");
+/// assert_eq!(s.remaining_capacity(), 4);
+/// assert!(s.try_push_str("!!!!!").is_err());
+/// ```
+pub struct StackString<const CAP: usize> {
+    pub(crate) bytes: StackVec<u8, CAP>,
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn capacity_overflow() -> ! {
+    panic!("Attempt to push beyond the capacity of the string")
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn length_not_on_char_boundary(len: usize) -> ! {
+    panic!("Length {len} is not on a char boundary")
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn range_not_on_char_boundary(start: usize, end: usize) -> ! {
+    panic!("Range {start}..{end} is not on a char boundary")
+}
+
+impl<const CAP: usize> StackString<CAP> {
+    /// Creates a new empty StackString
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            bytes: StackVec::new(),
+        }
+    }
+
+    /// Converts a StackVec of bytes into a StackString, without copying.
+    ///
+    /// # Errors
+    /// If the bytes are not valid UTF-8, returns a [FromUtf8Error],
+    /// from which the StackVec can be recovered.
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::{StackString, StackVec};
+    ///
+    /// let mut bytes = StackVec::<u8, 8>::new();
+    /// bytes.extend_from_iter(*b"caf\xC3\xA9");
+    ///
+    /// let s = StackString::from_utf8(bytes).unwrap();
+    /// assert_eq!(s.as_str(), "café");
+    ///
+    /// let bytes = s.into_bytes();
+    /// assert_eq!(bytes.len(), 5);
+    /// ```
+    pub fn from_utf8(bytes: StackVec<u8, CAP>) -> Result<Self, FromUtf8Error<CAP>> {
+        match str::from_utf8(&bytes) {
+            Ok(_) => Ok(Self { bytes }),
+            Err(error) => Err(FromUtf8Error { bytes, error }),
+        }
+    }
+
+    /// Converts a StackVec of bytes into a StackString,
+    /// without checking that they are valid UTF-8.
+    ///
+    /// # Safety
+    /// The bytes must be valid UTF-8
+    #[inline]
+    pub const unsafe fn from_utf8_unchecked(bytes: StackVec<u8, CAP>) -> Self {
+        Self { bytes }
+    }
+
+    /// Converts this StackString into a StackVec of bytes, without copying
+    #[inline]
+    pub fn into_bytes(self) -> StackVec<u8, CAP> {
+        self.bytes
+    }
+
+    /// Returns a mutable reference to the underlying StackVec of bytes
+    ///
+    /// # Safety
+    /// The bytes must be valid UTF-8 when the borrow ends.
+    /// Otherwise, the StackString would hold an invalid str.
+    #[inline]
+    pub const unsafe fn as_bytes_vec_mut(&mut self) -> &mut StackVec<u8, CAP> {
+        &mut self.bytes
+    }
+
+    /// Returns the contents of this StackString as a &str
+    #[inline]
+    pub const fn as_str(&self) -> &str {
+        /* SAFETY: The bytes are always valid UTF-8 */
+        unsafe { str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    /// Returns the contents of this StackString as a &mut str
+    #[inline]
+    pub const fn as_mut_str(&mut self) -> &mut str {
+        /* SAFETY: The bytes are always valid UTF-8 */
+        unsafe { str::from_utf8_unchecked_mut(self.bytes.as_slice_mut()) }
+    }
+
+    /// Returns the contents of this StackString as a byte slice
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Appends a char to the end of this StackString.
+    ///
+    /// # Panics
+    /// - If there's no room for the char
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, c: char) {
+        if self.try_push(c).is_err() {
+            capacity_overflow()
+        }
+    }
+
+    /// Attempts to append a char to the end of this StackString.
+    ///
+    /// # Errors
+    /// - If there's no room for the char, returns it back
+    ///   inside an Err variant.
+    pub fn try_push(&mut self, c: char) -> Result<(), char> {
+        let mut buf = [0; 4];
+        self.try_push_str(c.encode_utf8(&mut buf)).map_err(|_| c)
+    }
+
+    /// Appends a string slice to the end of this StackString.
+    ///
+    /// # Panics
+    /// - If there's no room for the string
+    #[inline]
+    #[track_caller]
+    pub fn push_str(&mut self, s: &str) {
+        if self.try_push_str(s).is_err() {
+            capacity_overflow()
+        }
+    }
+
+    /// Attempts to append a string slice to the end of this StackString.
+    ///
+    /// # Errors
+    /// If there's no room for the whole string, nothing is
+    /// appended, and a [CapacityError] is returned.
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        if s.len() > self.bytes.remaining_capacity() {
+            self.bytes.overflow("try_push_str");
+            return Err(CapacityError);
+        }
+        for &b in s.as_bytes() {
+            /* SAFETY: We've just checked that there's room for all the bytes */
+            unsafe { self.bytes.push_unchecked(b) };
+        }
+        Ok(())
+    }
+
+    /// Attempts to append all the chars from the iterator to this StackString.
+    ///
+    /// # Errors
+    /// If a char doesn't fit, a [CapacityError] is returned. The chars
+    /// appended up to that point are kept.
+    pub fn try_extend_chars<I>(&mut self, it: I) -> Result<(), CapacityError>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        it.into_iter()
+            .try_for_each(|c| self.try_push(c).map_err(|_| CapacityError))
+    }
+
+    /// Attempts to append all the string slices from the iterator to this StackString.
+    ///
+    /// # Errors
+    /// If a string slice doesn't fit, a [CapacityError] is returned.
+    /// The slices appended up to that point are kept.
+    pub fn try_extend_strs<'a, I>(&mut self, it: I) -> Result<(), CapacityError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        it.into_iter().try_for_each(|s| self.try_push_str(s))
+    }
+
+    /// Removes the last char of this StackString, and returns it.
+    /// If empty, returns None
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.truncate_to(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    /// Shortens this StackString to the given length, in bytes.
+    /// If `len` is greater than the current length, this has no effect.
+    ///
+    /// # Panics
+    /// - If `len` is not on a char boundary
+    #[track_caller]
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            if !self.as_str().is_char_boundary(len) {
+                length_not_on_char_boundary(len);
+            }
+            self.bytes.truncate_to(len);
+        }
+    }
+
+    /// Removes all the contents of this StackString
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    /// Removes the given range of bytes from this StackString,
+    /// and returns an iterator over the removed chars.
+    ///
+    /// If the iterator is dropped before being fully consumed,
+    /// the remaining chars in the range are removed anyway.
+    ///
+    /// # Panics
+    /// - If the range is reversed or out of bounds
+    /// - If the start or the end of the range are not on a char boundary
+    ///
+    /// # Example
+    /// ```
+    /// use stack_vector::StackString;
+    ///
+    /// let mut s = StackString::<16>::new();
+    /// s.push_str("α is alpha");
+    ///
+    /// let alpha = s.drain(..2).collect::<String>();
+    /// assert_eq!(alpha, "α");
+    /// assert_eq!(s.as_str(), " is alpha");
+    /// ```
+    #[track_caller]
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, CAP> {
+        let (start, end) = self.bytes.resolve_range(range);
+        if !(self.as_str().is_char_boundary(start) && self.as_str().is_char_boundary(end)) {
+            range_not_on_char_boundary(start, end);
+        }
+        Drain {
+            bytes: self.bytes.drain_unchecked(start, end),
+        }
+    }
+
+    /// Returns the capacity of this StackString, in bytes
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns how many more bytes can be pushed into this StackString
+    #[inline(always)]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.bytes.remaining_capacity()
+    }
+
+    /// Returns the length of this StackString, in bytes
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns true if the length is 0
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns true if no more bytes can be pushed into this StackString
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.bytes.is_full()
+    }
+}
+
+impl<const CAP: usize> Deref for StackString<CAP> {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const CAP: usize> DerefMut for StackString<CAP> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const CAP: usize> Default for StackString<CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> Clone for StackString<CAP> {
+    fn clone(&self) -> Self {
+        let mut s = Self::new();
+        s.push_str(self);
+        s
+    }
+}
+
+impl<const CAP: usize> Extend<char> for StackString<CAP> {
+    /// # Panics
+    /// - If the chars don't fit in the StackString
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|c| self.push(c));
+    }
+}
+
+impl<'a, const CAP: usize> Extend<&'a str> for StackString<CAP> {
+    /// # Panics
+    /// - If the string slices don't fit in the StackString
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|s| self.push_str(s));
+    }
+}
+
+impl<'a, const CAP: usize> TryFrom<&'a str> for StackString<CAP> {
+    type Error = CapacityError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut s = Self::new();
+        s.try_push_str(value)?;
+        Ok(s)
+    }
+}
+
+impl<const CAP: usize> fmt::Write for StackString<CAP> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const CAP: usize> fmt::Display for StackString<CAP> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for StackString<CAP> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const CAP: usize> PartialEq for StackString<CAP> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const CAP: usize> Eq for StackString<CAP> {}
+
+impl<const CAP: usize> PartialEq<str> for StackString<CAP> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const CAP: usize> PartialEq<&str> for StackString<CAP> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+/// The error returned by [StackString::from_utf8]
+pub struct FromUtf8Error<const CAP: usize> {
+    bytes: StackVec<u8, CAP>,
+    error: str::Utf8Error,
+}
+
+impl<const CAP: usize> FromUtf8Error<CAP> {
+    /// Returns the bytes that failed to convert
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns back the StackVec that failed to convert
+    #[inline]
+    pub fn into_bytes(self) -> StackVec<u8, CAP> {
+        self.bytes
+    }
+
+    /// Returns the underlying [Utf8Error](str::Utf8Error),
+    /// with details about the conversion failure
+    #[inline]
+    pub fn utf8_error(&self) -> str::Utf8Error {
+        self.error
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for FromUtf8Error<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FromUtf8Error")
+            .field("bytes", &self.as_bytes())
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<const CAP: usize> fmt::Display for FromUtf8Error<CAP> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<const CAP: usize> core::error::Error for FromUtf8Error<CAP> {}
+
+impl<const CAP: usize> From<StackString<CAP>> for StackVec<u8, CAP> {
+    #[inline]
+    fn from(s: StackString<CAP>) -> Self {
+        s.into_bytes()
+    }
+}
+
+/// A draining iterator over the chars of a [StackString].
+///
+/// This struct is created by [StackString::drain].
+pub struct Drain<'a, const CAP: usize> {
+    bytes: crate::Drain<'a, u8, CAP>,
+}
+
+impl<const CAP: usize> Drain<'_, CAP> {
+    /// Returns the chars that haven't been drained yet, as a &str
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        /* SAFETY: The drained range starts and ends on char boundaries,
+         * and we always advance the inner Drain one whole char at a time */
+        unsafe { str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+}
+
+impl<const CAP: usize> Iterator for Drain<'_, CAP> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next()?;
+        self.bytes.nth(c.len_utf8() - 1);
+        Some(c)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.as_slice().len();
+        (len.div_ceil(4), Some(len))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<char> {
+        self.next_back()
+    }
+}
+
+impl<const CAP: usize> DoubleEndedIterator for Drain<'_, CAP> {
+    fn next_back(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.nth_back(c.len_utf8() - 1);
+        Some(c)
+    }
+}
+
+impl<const CAP: usize> FusedIterator for Drain<'_, CAP> {}
@@ -0,0 +1,78 @@
+use core::fmt;
+
+use crate::{StackString, StackVec};
+
+/// A [fmt::Write] adapter over a byte buffer that never fails.
+///
+/// When the buffer is full, the output is truncated, and the number of
+/// bytes that didn't fit is recorded. The output is truncated on a char
+/// boundary, and nothing is written after the first truncation, so the
+/// contents of the buffer are always a valid UTF-8 prefix of the output.
+///
+/// This is useful for panic handlers and log sinks, where a
+/// truncated message is better than no message.
+///
+/// # Example
+/// ```
+/// use core::fmt::Write;
+/// use stack_vector::{StackString, TruncatingWriter};
+///
+/// let mut msg = StackString::<8>::new();
+/// let mut w = TruncatingWriter::for_string(&mut msg);
+///
+/// write!(w, "error: {}", 42).unwrap();
+/// assert_eq!(w.dropped(), 1);
+///
+/// assert_eq!(msg.as_str(), "error: 4");
+/// ```
+pub struct TruncatingWriter<'a, const CAP: usize> {
+    buf: &'a mut StackVec<u8, CAP>,
+    dropped: usize,
+}
+
+impl<'a, const CAP: usize> TruncatingWriter<'a, CAP> {
+    /// Creates a TruncatingWriter that appends to the given StackVec
+    #[inline]
+    pub fn new(buf: &'a mut StackVec<u8, CAP>) -> Self {
+        Self { buf, dropped: 0 }
+    }
+
+    /// Creates a TruncatingWriter that appends to the given StackString
+    #[inline]
+    pub fn for_string(s: &'a mut StackString<CAP>) -> Self {
+        /* Since we only write whole chars, the
+         * string is always valid UTF-8 */
+        Self::new(&mut s.bytes)
+    }
+
+    /// Returns the number of bytes that didn't fit in the buffer
+    #[inline(always)]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns true if the output has been truncated
+    #[inline(always)]
+    pub fn is_truncated(&self) -> bool {
+        self.dropped > 0
+    }
+}
+
+impl<const CAP: usize> fmt::Write for TruncatingWriter<'_, CAP> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.dropped > 0 {
+            self.dropped += s.len();
+            return Ok(());
+        }
+        let mut n = s.len().min(self.buf.remaining_capacity());
+        while !s.is_char_boundary(n) {
+            n -= 1;
+        }
+        for &b in &s.as_bytes()[..n] {
+            /* SAFETY: There's room for the first n bytes */
+            unsafe { self.buf.push_unchecked(b) };
+        }
+        self.dropped = s.len() - n;
+        Ok(())
+    }
+}
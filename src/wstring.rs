@@ -0,0 +1,234 @@
+//! A UTF-16 string with a fixed capacity
+//!
+//! See [StackWString]
+
+use core::char;
+use core::fmt;
+
+use crate::{CapacityError, StackVec};
+
+/// A UTF-16 encoded string with a fixed capacity of CAP code units.
+///
+/// Wide strings are needed to call Windows and UEFI APIs. This type
+/// allows building them without allocating, and passing them as
+/// NUL-terminated buffers with [as_nul_terminated](Self::as_nul_terminated).
+///
+/// # Example
+/// ```
+/// use stack_vector::StackWString;
+///
+/// let mut path = StackWString::<16>::try_from("C:\\").unwrap();
+/// path.push_str("ñ").unwrap();
+///
+/// assert_eq!(path.as_slice(), &[b'C' as u16, b':' as u16, b'\\' as u16, 0xF1]);
+/// assert_eq!(path.as_nul_terminated().unwrap().last(), Some(&0));
+/// assert_eq!(path.to_string(), "C:\\ñ");
+/// ```
+pub struct StackWString<const CAP: usize> {
+    units: StackVec<u16, CAP>,
+}
+
+impl<const CAP: usize> StackWString<CAP> {
+    /// Creates a new empty StackWString
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            units: StackVec::new(),
+        }
+    }
+
+    /// Returns the UTF-16 code units of this string, without a NUL terminator
+    #[inline]
+    pub const fn as_slice(&self) -> &[u16] {
+        self.units.as_slice()
+    }
+
+    /// Returns the code units of this string followed by a NUL terminator.
+    ///
+    /// The terminator is written right after the string, but it's not
+    /// part of it, so the length doesn't change. Keep in mind that, if
+    /// the string contains '\0', the APIs reading it will stop there.
+    ///
+    /// # Errors
+    /// If the string is full, there's no room for the terminator
+    pub fn as_nul_terminated(&mut self) -> Result<&[u16], CapacityError> {
+        let len = self.units.len();
+        let Some(slot) = self.units.spare_capacity_mut().first_mut() else {
+            self.units.overflow("as_nul_terminated");
+            return Err(CapacityError);
+        };
+        slot.write(0);
+        let ptr = self.units.as_ptr();
+        /* SAFETY: The first len + 1 units are initialized */
+        Ok(unsafe { core::slice::from_raw_parts(ptr, len + 1) })
+    }
+
+    /// Attempts to append a char to the end of this string.
+    ///
+    /// # Errors
+    /// - If there's no room for the char, returns it back
+    ///   inside an Err variant.
+    pub fn push(&mut self, c: char) -> Result<(), char> {
+        let mut buf = [0; 2];
+        let units = c.encode_utf16(&mut buf);
+        if units.len() > self.units.remaining_capacity() {
+            self.units.overflow("push");
+            return Err(c);
+        }
+        for &u in units.iter() {
+            /* SAFETY: We've just checked that there's room for the units */
+            unsafe { self.units.push_unchecked(u) };
+        }
+        Ok(())
+    }
+
+    /// Attempts to append a string slice to the end of this string.
+    ///
+    /// # Errors
+    /// If there's no room for the whole string, nothing is
+    /// appended, and a [CapacityError] is returned.
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        let n = s.encode_utf16().count();
+        self.units.ensure_remaining(n)?;
+        for u in s.encode_utf16() {
+            /* SAFETY: We've just checked that there's room for the units */
+            unsafe { self.units.push_unchecked(u) };
+        }
+        Ok(())
+    }
+
+    /// Removes the last char of this string, and returns it.
+    /// If empty, returns None
+    pub fn pop(&mut self) -> Option<char> {
+        let &last = self.units.last()?;
+        /* A low surrogate is the second half of a pair */
+        let n = if (0xDC00..=0xDFFF).contains(&last) && self.len() >= 2 {
+            2
+        } else {
+            1
+        };
+        let start = self.len() - n;
+        let c = Self::decode(&self.units[start..]).next()?;
+        self.units.truncate_to(start);
+        Some(c)
+    }
+
+    fn decode(units: &[u16]) -> impl Iterator<Item = char> {
+        /* The units are always valid UTF-16, since we only push whole chars */
+        char::decode_utf16(units.iter().copied()).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    /// Returns an iterator over the chars of this string
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        Self::decode(self.as_slice())
+    }
+
+    /// Removes all the contents of this string
+    #[inline]
+    pub fn clear(&mut self) {
+        self.units.clear();
+    }
+
+    /// Returns the capacity of this StackWString, in code units
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Returns how many more code units can be pushed into this string
+    #[inline(always)]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.units.remaining_capacity()
+    }
+
+    /// Returns the length of this string, in code units
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.units.len()
+    }
+
+    /// Returns true if the length is 0
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.units.is_empty()
+    }
+
+    /// Returns true if no more code units can be pushed into this string
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.units.is_full()
+    }
+}
+
+impl<const CAP: usize> Default for StackWString<CAP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAP: usize> Clone for StackWString<CAP> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            units: self.units.clone(),
+        }
+    }
+}
+
+impl<'a, const CAP: usize> TryFrom<&'a str> for StackWString<CAP> {
+    type Error = CapacityError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        let mut s = Self::new();
+        s.push_str(value)?;
+        Ok(s)
+    }
+}
+
+impl<const CAP: usize> fmt::Write for StackWString<CAP> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const CAP: usize> fmt::Display for StackWString<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chars().try_for_each(|c| fmt::Write::write_char(f, c))
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for StackWString<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.chars() {
+            fmt::Display::fmt(&c.escape_debug(), f)?;
+        }
+        f.write_str("\"")
+    }
+}
+
+impl<const CAP: usize> PartialEq for StackWString<CAP> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const CAP: usize> Eq for StackWString<CAP> {}
+
+impl<const CAP: usize> PartialEq<str> for StackWString<CAP> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_slice().iter().copied().eq(other.encode_utf16())
+    }
+}
+
+impl<const CAP: usize> PartialEq<&str> for StackWString<CAP> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
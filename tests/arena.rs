@@ -0,0 +1,21 @@
+use stack_vector::StackArena;
+
+#[test]
+fn alignment_and_reset() {
+    let mut arena = StackArena::<32>::new();
+
+    let a = arena.alloc(1_u8).unwrap();
+    let b = arena.alloc(2_u64).unwrap();
+    assert_eq!(b as *mut u64 as usize % align_of::<u64>(), 0);
+    assert_eq!((*a, *b), (1, 2));
+
+    let s = arena.alloc_slice(&[7_u32; 3]).unwrap();
+    assert_eq!(s, &[7, 7, 7]);
+    assert!(arena.alloc_slice(&[0_u64; 2]).is_err());
+    assert_eq!(arena.alloc([0_u8; 32]), Err([0; 32]));
+
+    arena.reset();
+    assert_eq!(arena.remaining(), 32);
+    assert!(arena.alloc([0_u8; 32]).is_ok());
+    assert!(arena.alloc(()).is_ok());
+}
@@ -0,0 +1,49 @@
+use stack_vector::AtomicStackVec;
+
+#[test]
+fn concurrent_push() {
+    let v = AtomicStackVec::<usize, 100>::new();
+
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let v = &v;
+            s.spawn(move || {
+                for i in 0..30 {
+                    let _ = v.push(t * 1000 + i);
+                }
+            });
+        }
+        /* Snapshots can be taken while pushing */
+        let snapshot = v.as_slice();
+        assert!(snapshot.len() <= 100);
+    });
+
+    assert!(v.is_full());
+    assert_eq!(v.len(), 100);
+    assert_eq!(v.push(0), Err(0));
+
+    let mut elems = v.as_slice().to_vec();
+    elems.sort_unstable();
+    elems.dedup();
+    assert_eq!(elems.len(), 100);
+}
+
+#[test]
+fn every_push_is_published() {
+    /* Many short rounds, so that pushers often finish
+     * adjacent slots at the same time */
+    for _ in 0..200 {
+        let v = AtomicStackVec::<usize, 64>::new();
+        std::thread::scope(|s| {
+            for t in 0..8 {
+                let v = &v;
+                s.spawn(move || {
+                    for i in 0..8 {
+                        v.push(t * 8 + i).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(v.len(), 64);
+    }
+}
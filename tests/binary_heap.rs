@@ -0,0 +1,67 @@
+use stack_vector::StackBinaryHeap;
+use stack_vector::binary_heap::PeekMut;
+
+#[test]
+fn push_pop() {
+    let mut heap = StackBinaryHeap::<i32, 6>::new();
+    for n in [5, 1, 8, 3, 9, 2] {
+        heap.push(n).unwrap();
+    }
+    assert!(heap.is_full());
+    assert_eq!(heap.push(7), Err(7));
+
+    let popped = core::iter::from_fn(|| heap.pop()).collect::<Vec<_>>();
+    assert_eq!(popped, [9, 8, 5, 3, 2, 1]);
+    assert_eq!(heap.peek(), None);
+}
+
+#[test]
+fn into_sorted_vec() {
+    let mut heap = StackBinaryHeap::<i32, 8>::new();
+    for n in [4, 4, 7, 0, -3, 12, 7] {
+        heap.push(n).unwrap();
+    }
+    assert_eq!(heap.into_sorted_vec().as_slice(), &[-3, 0, 4, 4, 7, 7, 12]);
+}
+
+#[test]
+fn peek_mut() {
+    let mut heap = StackBinaryHeap::<i32, 4>::new();
+    for n in [10, 6, 8] {
+        heap.push(n).unwrap();
+    }
+
+    *heap.peek_mut().unwrap() -= 5;
+    assert_eq!(heap.peek(), Some(&8));
+
+    {
+        let mut top = heap.peek_mut().unwrap();
+        *top = 20;
+    }
+    assert_eq!(heap.peek(), Some(&20));
+
+    let top = heap.peek_mut().unwrap();
+    assert_eq!(PeekMut::pop(top), 20);
+    assert_eq!(heap.into_sorted_vec().as_slice(), &[5, 6]);
+
+    let mut empty = StackBinaryHeap::<i32, 4>::new();
+    assert!(empty.peek_mut().is_none());
+}
+
+#[test]
+fn drain_sorted() {
+    let mut heap = StackBinaryHeap::<i32, 5>::new();
+    for n in [3, 1, 4, 1, 5] {
+        heap.push(n).unwrap();
+    }
+
+    let drain = heap.drain_sorted();
+    assert_eq!(drain.len(), 5);
+    assert_eq!(drain.collect::<Vec<_>>(), [5, 4, 3, 1, 1]);
+    assert!(heap.is_empty());
+
+    heap.push(2).unwrap();
+    heap.push(9).unwrap();
+    assert_eq!(heap.drain_sorted().next(), Some(9));
+    assert!(heap.is_empty());
+}
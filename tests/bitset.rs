@@ -0,0 +1,69 @@
+use stack_vector::StackBitSet;
+use stack_vector::bitset::words_for;
+
+#[test]
+fn set_clear() {
+    let mut bs = StackBitSet::<70, { words_for(70) }>::new();
+    assert!(bs.is_empty());
+    assert_eq!(bs.capacity(), 70);
+
+    for i in (0..70).step_by(3) {
+        bs.set(i);
+    }
+    assert_eq!(bs.count_ones(), 24);
+    assert_eq!(bs.count_zeros(), 46);
+    assert!(bs.test(69));
+    assert!(!bs.test(68));
+    assert!(!bs.test(1000));
+
+    bs.clear(69);
+    assert!(!bs.test(69));
+    assert!(bs.try_set(70).is_err());
+    assert!(bs.try_clear(70).is_err());
+
+    for i in 0..70 {
+        bs.set(i);
+    }
+    assert!(bs.is_full());
+    bs.clear_all();
+    assert!(bs.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Attemp to set a bit beyond the capacity of the bitset")]
+fn set_out_of_bounds_must_panic() {
+    let mut bs = StackBitSet::<8, 1>::new();
+    bs.set(8);
+}
+
+#[test]
+fn bit_operations() {
+    type Bs = StackBitSet<40, { words_for(40) }>;
+
+    let mut a = Bs::new();
+    let mut b = Bs::new();
+    for i in [1, 5, 33] {
+        a.set(i);
+    }
+    for i in [5, 33, 39] {
+        b.set(i);
+    }
+
+    assert!((a & b).iter_ones().eq([5, 33]));
+    assert!((a | b).iter_ones().eq([1, 5, 33, 39]));
+    assert!((a ^ b).iter_ones().eq([1, 39]));
+
+    let not_a = !a;
+    assert_eq!(not_a.count_ones(), 37);
+    assert!(!not_a.test(1));
+    assert_eq!(not_a.iter_ones().last(), Some(39));
+    assert!(a.iter_zeros().eq(not_a.iter_ones()));
+
+    assert_eq!(a.first_one(), Some(1));
+    assert_eq!(a.first_zero(), Some(0));
+    assert_eq!(Bs::new().first_one(), None);
+    assert_eq!((!Bs::new()).first_zero(), None);
+
+    a |= b;
+    assert_eq!(a.count_ones(), 4);
+}
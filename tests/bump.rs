@@ -0,0 +1,105 @@
+use std::alloc::{GlobalAlloc, Layout};
+
+use stack_vector::StackBumpAlloc;
+
+#[test]
+fn alignment_and_exhaustion() {
+    let alloc = StackBumpAlloc::<64>::new();
+
+    let byte = Layout::new::<u8>();
+    let word = Layout::new::<u64>();
+
+    let a = unsafe { alloc.alloc(byte) };
+    let b = unsafe { alloc.alloc(word) };
+    assert!(!a.is_null() && !b.is_null());
+    assert_eq!(b as usize % 8, 0);
+    assert!(b as usize > a as usize);
+
+    unsafe { b.cast::<u64>().write(u64::MAX) };
+
+    let rest = Layout::from_size_align(alloc.remaining() + 1, 1).unwrap();
+    assert!(unsafe { alloc.alloc(rest) }.is_null());
+
+    let rest = Layout::from_size_align(alloc.remaining(), 1).unwrap();
+    assert!(!unsafe { alloc.alloc(rest) }.is_null());
+    assert_eq!(alloc.remaining(), 0);
+    assert!(unsafe { alloc.alloc(byte) }.is_null());
+
+    assert_eq!(unsafe { b.cast::<u64>().read() }, u64::MAX);
+}
+
+#[test]
+fn lifo_dealloc() {
+    let mut alloc = StackBumpAlloc::<32>::new();
+    let layout = Layout::new::<u32>();
+
+    let a = unsafe { alloc.alloc(layout) };
+    let used = alloc.used();
+    let b = unsafe { alloc.alloc(layout) };
+
+    /* a is not the most recent allocation, so nothing is reclaimed */
+    unsafe { alloc.dealloc(a, layout) };
+    assert_eq!(alloc.used(), used + 4);
+
+    unsafe { alloc.dealloc(b, layout) };
+    assert_eq!(alloc.used(), used);
+
+    alloc.reset();
+    assert_eq!(alloc.used(), 0);
+    assert_eq!(alloc.remaining(), alloc.capacity());
+}
+
+#[test]
+fn realloc() {
+    let alloc = StackBumpAlloc::<64>::new();
+    let layout = Layout::from_size_align(4, 4).unwrap();
+
+    let a = unsafe { alloc.alloc(layout) };
+    unsafe { a.copy_from([1, 2, 3, 4].as_ptr(), 4) };
+
+    /* The most recent allocation grows in place */
+    let a2 = unsafe { alloc.realloc(a, layout, 16) };
+    assert_eq!(a, a2);
+
+    let b = unsafe { alloc.alloc(layout) };
+    assert!(!b.is_null());
+
+    /* Otherwise, it's moved */
+    let big = Layout::from_size_align(16, 4).unwrap();
+    let a3 = unsafe { alloc.realloc(a2, big, 24) };
+    assert!(!a3.is_null());
+    assert_ne!(a3, a2);
+    assert_eq!(unsafe { core::slice::from_raw_parts(a3, 4) }, &[1, 2, 3, 4]);
+
+    /* No room to grow */
+    let new_layout = Layout::from_size_align(24, 4).unwrap();
+    assert!(unsafe { alloc.realloc(a3, new_layout, 64) }.is_null());
+}
+
+#[test]
+fn concurrent_allocations() {
+    let alloc = StackBumpAlloc::<1024>::new();
+    let layout = Layout::new::<u64>();
+
+    let ptrs = std::thread::scope(|s| {
+        let handles = (0..4)
+            .map(|_| {
+                s.spawn(|| {
+                    (0..16)
+                        .map(|_| unsafe { alloc.alloc(layout) } as usize)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    let mut sorted = ptrs.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted.len(), 64);
+    assert!(sorted.windows(2).all(|w| w[1] - w[0] >= 8));
+}
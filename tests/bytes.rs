@@ -0,0 +1,90 @@
+use stack_vector::StackVec;
+use stack_vector::bytes::{StackCursor, UnderrunError};
+
+#[test]
+fn read() {
+    let mut buf = StackVec::<u8, 32>::new();
+    buf.extend_from_iter([0xAB, 0x12, 0x34, 0x12, 0x34]);
+    buf.extend_from_iter(0xDEADBEEF_u32.to_le_bytes());
+    buf.extend_from_iter(7_u64.to_be_bytes());
+
+    let mut cur = StackCursor::new(&buf);
+    assert_eq!(cur.read_u8(), Ok(0xAB));
+    assert_eq!(cur.read_u16_be(), Ok(0x1234));
+    assert_eq!(cur.read_u16_le(), Ok(0x3412));
+    assert_eq!(cur.read_u32_le(), Ok(0xDEADBEEF));
+    assert_eq!(cur.position(), 9);
+    assert_eq!(cur.remaining(), 8);
+
+    assert_eq!(cur.read_array::<9>(), Err(UnderrunError));
+    assert_eq!(cur.position(), 9);
+    assert_eq!(cur.read_u64_be(), Ok(7));
+    assert_eq!(cur.read_bytes(0), Ok(&[][..]));
+    assert_eq!(cur.read_u8(), Err(UnderrunError));
+
+    cur.set_position(1).unwrap();
+    cur.skip(2).unwrap();
+    assert_eq!(cur.remaining_slice()[..2], [0x12, 0x34]);
+    assert!(cur.set_position(18).is_err());
+}
+
+#[test]
+fn write() {
+    let mut buf = StackVec::<u8, 16>::new();
+    buf.put_u8(1).unwrap();
+    buf.put_u16_be(0x0203).unwrap();
+    buf.put_u32_le(0x07060504).unwrap();
+    buf.put_u64_be(0x08090A0B0C0D0E0F).unwrap();
+    assert_eq!(buf.as_slice(), (1..16).collect::<Vec<u8>>());
+
+    assert!(buf.put_u16_le(0).is_err());
+    assert!(buf.put_slice(&[]).is_ok());
+    buf.put_slice(&[16]).unwrap();
+    assert!(buf.is_full());
+
+    let mut cur = StackCursor::new(&buf);
+    cur.skip(3).unwrap();
+    assert_eq!(cur.read_u32_le(), Ok(0x07060504));
+}
+
+#[test]
+#[cfg(feature = "varint")]
+fn varint() {
+    use stack_vector::bytes::VarintError;
+
+    let values = [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX];
+    let mut buf = StackVec::<u8, 32>::new();
+    for n in values {
+        buf.put_varint_u64(n).unwrap();
+    }
+    assert_eq!(buf.len(), 1 + 1 + 1 + 2 + 2 + 5 + 10);
+
+    let mut cur = StackCursor::new(&buf);
+    for n in values {
+        assert_eq!(cur.read_varint_u64(), Ok(n));
+    }
+    assert_eq!(cur.read_varint_u64(), Err(VarintError::Underrun));
+
+    let mut cur = StackCursor::new(&buf);
+    cur.skip(7).unwrap();
+    assert_eq!(cur.read_varint_u32(), Ok(u32::MAX));
+    assert_eq!(cur.read_varint_u32(), Err(VarintError::Overflow));
+    assert_eq!(cur.position(), 12);
+
+    let mut buf = StackVec::<u8, 11>::new();
+    buf.extend_from_iter([0xFF; 9]);
+    buf.push(0x02);
+    assert_eq!(
+        StackCursor::new(&buf).read_varint_u64(),
+        Err(VarintError::Overflow)
+    );
+    buf.pop();
+    assert_eq!(
+        StackCursor::new(&buf).read_varint_u64(),
+        Err(VarintError::Underrun)
+    );
+
+    let mut small = StackVec::<u8, 1>::new();
+    assert!(small.put_varint_u32(128).is_err());
+    assert!(small.is_empty());
+}
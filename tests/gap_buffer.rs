@@ -0,0 +1,81 @@
+use core::fmt::Write;
+
+use stack_vector::{StackGapBuffer, StackString};
+
+#[test]
+fn edit_at_cursor() {
+    let mut gb = StackGapBuffer::<8>::new();
+    gb.insert_str("añb").unwrap();
+    assert_eq!(gb.len(), 4);
+
+    assert!(gb.move_left());
+    assert!(gb.move_left());
+    assert_eq!(gb.cursor(), 1);
+    assert_eq!(gb.before(), "a");
+    assert_eq!(gb.after(), "ñb");
+
+    gb.insert('x').unwrap();
+    assert_eq!(gb, "axñb");
+    assert_eq!(gb.delete_forward(), Some('ñ'));
+    assert_eq!(gb.delete_backward(), Some('x'));
+    assert_eq!(gb, "ab");
+
+    gb.move_to_start();
+    assert!(!gb.move_left());
+    assert_eq!(gb.delete_backward(), None);
+    gb.move_to_end();
+    assert!(!gb.move_right());
+    assert_eq!(gb.delete_forward(), None);
+}
+
+#[test]
+fn capacity() {
+    let mut gb = StackGapBuffer::<4>::try_from("abc").unwrap();
+    gb.move_to(1);
+    assert_eq!(gb.insert('ñ'), Err('ñ'));
+    assert!(gb.insert_str("xy").is_err());
+    gb.insert('x').unwrap();
+    assert!(gb.is_full());
+    assert_eq!(gb.to_string(), "axbc");
+    assert!(write!(gb, "y").is_err());
+
+    gb.clear();
+    assert!(gb.is_empty());
+    assert_eq!(gb.remaining_capacity(), 4);
+}
+
+#[test]
+fn move_to() {
+    let mut gb = StackGapBuffer::<16>::try_from("hello world").unwrap();
+    gb.move_to(5);
+    assert_eq!(gb.before(), "hello");
+    gb.move_to(0);
+    gb.move_to(11);
+    assert_eq!(gb.after(), "");
+    gb.move_to(6);
+    assert_eq!(gb.after(), "world");
+    write!(gb, "{}, ", 42).unwrap();
+    assert_eq!(gb, "hello 42, world");
+}
+
+#[test]
+#[should_panic(expected = "Position 2 is not on a char boundary")]
+fn move_to_char_boundary() {
+    let mut gb = StackGapBuffer::<8>::try_from("añ").unwrap();
+    gb.move_to(0);
+    gb.move_to(2);
+}
+
+#[test]
+fn string_conversions() {
+    let s = StackString::<8>::try_from("abc").unwrap();
+    let mut gb = StackGapBuffer::from(s);
+    assert_eq!(gb.cursor(), 3);
+    gb.move_to(1);
+    gb.insert('-').unwrap();
+
+    let s: StackString<8> = gb.clone().into();
+    assert_eq!(s, "a-bc");
+    assert_eq!(gb, StackGapBuffer::try_from("a-bc").unwrap());
+    assert_ne!(gb, StackGapBuffer::try_from("a-b").unwrap());
+}
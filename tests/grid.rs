@@ -0,0 +1,61 @@
+use stack_vector::StackGrid;
+
+#[test]
+fn rows_and_columns() {
+    let mut grid = StackGrid::<char, 3, 2>::from_rows([['a', 'b', 'c'], ['d', 'e', 'f']]);
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+
+    let rows = grid
+        .rows()
+        .map(|r| r.iter().collect::<String>())
+        .collect::<Vec<_>>();
+    assert_eq!(rows, ["abc", "def"]);
+
+    let cols = grid
+        .columns()
+        .map(|c| c.collect::<String>())
+        .collect::<Vec<_>>();
+    assert_eq!(cols, ["ad", "be", "cf"]);
+    assert_eq!(grid.columns().len(), 3);
+    assert_eq!(
+        grid.columns()
+            .next_back()
+            .unwrap()
+            .rev()
+            .collect::<String>(),
+        "fc"
+    );
+
+    assert!(grid.column(3).is_none());
+    assert_eq!(grid.column(1).unwrap().len(), 2);
+
+    grid.row_mut(1).unwrap()[0] = 'x';
+    *grid.get_mut(0, 2).unwrap() = 'y';
+    assert_eq!(grid.get(1, 0), Some(&'x'));
+    assert_eq!(grid.get(2, 0), None);
+    assert_eq!(grid.as_flattened(), &['a', 'b', 'y', 'x', 'e', 'f']);
+}
+
+#[test]
+fn iter_indexed() {
+    let grid = StackGrid::<usize, 4, 3>::from_fn(|row, col| row * 10 + col);
+    for ((row, col), &v) in grid.iter_indexed() {
+        assert_eq!(v, row * 10 + col);
+    }
+    assert_eq!(grid.iter_indexed().len(), 12);
+    assert_eq!(grid.iter_indexed().next_back(), Some(((2, 3), &23)));
+}
+
+#[test]
+fn transpose() {
+    let mut grid = StackGrid::<u32, 3, 3>::from_fn(|row, col| (row * 3 + col) as u32);
+    grid.transpose();
+    assert_eq!(grid.into_rows(), [[0, 3, 6], [1, 4, 7], [2, 5, 8]]);
+
+    let mut single = StackGrid::from_rows([[String::from("x")]]);
+    single.transpose();
+    assert_eq!(single[(0, 0)], "x");
+
+    let mut empty = StackGrid::<u8, 0, 0>::default();
+    empty.transpose();
+}
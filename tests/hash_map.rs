@@ -0,0 +1,79 @@
+use stack_vector::StackHashMap;
+
+#[test]
+fn insert_remove() {
+    let mut map = StackHashMap::<u32, u32, 16>::new();
+    assert_eq!(StackHashMap::<u32, u32, 16>::max_len(), 12);
+
+    for i in 0..12 {
+        assert_eq!(map.try_insert(i, i * 10), Ok(None));
+    }
+    assert!(map.is_full());
+    assert_eq!(map.try_insert(100, 0), Err((100, 0)));
+    assert_eq!(map.try_insert(3, 33), Ok(Some(30)));
+
+    for i in 0..12 {
+        assert!(map.contains_key(&i));
+    }
+
+    /* Churn through removals and insertions, so
+     * tombstones pile up and force a rehash */
+    for round in 0..10 {
+        for i in 0..6 {
+            assert!(map.remove(&(i + round * 100)).is_some());
+        }
+        for i in 0..6 {
+            map.insert(i + (round + 1) * 100, i);
+        }
+        assert_eq!(map.len(), 12);
+    }
+
+    for i in 6..12 {
+        assert_eq!(map.get(&i), Some(&(i * 10)));
+    }
+    for i in 0..6 {
+        assert_eq!(map.get(&(i + 1000)), Some(&i));
+    }
+    assert_eq!(map.iter().count(), 12);
+}
+
+#[test]
+fn custom_hasher() {
+    use stack_vector::hash_map::IdentityBuildHasher;
+
+    let mut map = StackHashMap::<u64, u64, 8, IdentityBuildHasher>::default();
+    for i in (0..48).step_by(8) {
+        /* All these keys collide with the identity hasher */
+        map.insert(i, i / 8);
+    }
+    for i in (0..48).step_by(8) {
+        assert_eq!(map.get(&i), Some(&(i / 8)));
+    }
+    assert_eq!(map.try_insert(1, 0), Err((1, 0)));
+}
+
+#[test]
+#[should_panic(expected = "Attempt to insert beyond the capacity of the map")]
+fn insert_full() {
+    let mut map = StackHashMap::<u8, u8, 4>::new();
+    for i in 0..4 {
+        map.insert(i, i);
+    }
+}
+
+#[test]
+fn identity_hasher_folds() {
+    use core::hash::BuildHasher;
+    use stack_vector::hash_map::IdentityBuildHasher;
+
+    let hasher = IdentityBuildHasher::default();
+    assert_eq!(hasher.hash_one(42u32), 42);
+    assert_ne!(hasher.hash_one("a"), hasher.hash_one("b"));
+    assert_ne!(hasher.hash_one((1u16, 2u16)), hasher.hash_one((3u16, 2u16)));
+
+    let mut map = StackHashMap::<&str, usize, 8, IdentityBuildHasher>::default();
+    for (i, k) in ["a", "bb", "ccc", "dddd"].into_iter().enumerate() {
+        map.insert(k, i);
+    }
+    assert_eq!(map.get("ccc"), Some(&2));
+}
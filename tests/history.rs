@@ -0,0 +1,40 @@
+use std::rc::Rc;
+
+use stack_vector::HistoryBuffer;
+
+#[test]
+fn overwrite_oldest() {
+    let mut h = HistoryBuffer::<i32, 4>::new();
+    assert_eq!(h.recent(), None);
+    assert_eq!(h.oldest(), None);
+
+    h.extend_from_iter(0..3);
+    assert_eq!(h.as_slices(), (&[0, 1, 2][..], &[][..]));
+    assert_eq!(h.write(3), None);
+    assert!(h.is_full());
+
+    assert_eq!(h.write(4), Some(0));
+    assert_eq!(h.write(5), Some(1));
+    assert_eq!(h.as_slices(), (&[2, 3][..], &[4, 5][..]));
+    assert!(h.iter().rev().eq(&[5, 4, 3, 2]));
+    assert_eq!(h.recent(), Some(&5));
+    assert_eq!(h.oldest(), Some(&2));
+
+    h.clear();
+    assert!(h.is_empty());
+    h.write(6);
+    assert!(h.iter().eq(&[6]));
+}
+
+#[test]
+fn drops_elements() {
+    let rc = Rc::new(());
+    {
+        let mut h = HistoryBuffer::<_, 3>::new();
+        for _ in 0..5 {
+            h.write(rc.clone());
+        }
+        assert_eq!(Rc::strong_count(&rc), 4);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "overflow-hook")]
+
+use std::sync::Mutex;
+
+use stack_vector::hook::{self, OverflowEvent};
+use stack_vector::{StackHashMap, StackMap, StackVec};
+
+static EVENTS: Mutex<Vec<OverflowEvent>> = Mutex::new(Vec::new());
+
+fn record(ev: &OverflowEvent) {
+    EVENTS.lock().unwrap().push(*ev);
+}
+
+#[test]
+fn overflow_events() {
+    hook::set_overflow_hook(record);
+
+    let mut sv = StackVec::<u16, 2>::new();
+    sv.try_push(1).unwrap();
+    sv.try_push(2).unwrap();
+    assert!(sv.try_push(3).is_err());
+    assert!(sv.try_extend_from_iter([4]).is_err());
+
+    let mut map = StackMap::<u8, u8, 1>::new();
+    map.insert(1, 1).unwrap();
+    assert!(map.entry(2).or_insert_with(|| 2).is_err());
+
+    let mut hash_map = StackHashMap::<u8, u8, 4>::new();
+    for i in 0..3 {
+        hash_map.insert(i, i);
+    }
+    assert!(hash_map.try_insert(3, 3).is_err());
+
+    assert!(hook::take_overflow_hook().is_some());
+    assert!(sv.try_push(5).is_err());
+
+    let events = EVENTS.lock().unwrap();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].type_name, "u16");
+    assert_eq!(events[0].capacity, 2);
+    assert_eq!(events[0].len, 2);
+    assert_eq!(events[0].operation, "try_push");
+    assert_eq!(events[1].operation, "try_extend_from_iter");
+    assert_eq!(events[2].type_name, "(u8, u8)");
+    assert_eq!(events[2].operation, "or_insert_with");
+    assert_eq!(events[3].capacity, 3);
+    assert_eq!(events[3].len, 3);
+    assert_eq!(events[3].operation, "try_insert");
+}
@@ -0,0 +1,70 @@
+use stack_vector::StackLinkedList;
+
+fn contents<const CAP: usize>(list: &StackLinkedList<i32, CAP>) -> Vec<i32> {
+    list.iter().map(|(_, v)| *v).collect()
+}
+
+#[test]
+fn insert_remove() {
+    let mut list = StackLinkedList::<i32, 4>::new();
+    assert_eq!(list.pop_front(), None);
+
+    let two = list.push_back(2).unwrap();
+    let one = list.push_front(1).unwrap();
+    list.insert_after(two, 4).unwrap();
+    let three = list.insert_before(list.back_handle().unwrap(), 3).unwrap();
+    assert_eq!(contents(&list), [1, 2, 3, 4]);
+    assert!(list.is_full());
+    assert_eq!(list.push_back(5), Err(5));
+
+    assert_eq!(list.next(one), Some(two));
+    assert_eq!(list.prev(two), Some(one));
+    assert_eq!(list.prev(one), None);
+
+    assert_eq!(list.remove(two), Some(2));
+    assert_eq!(list.remove(two), None);
+    assert_eq!(list.insert_after(two, 9), Err(9));
+    assert_eq!(list.next(one), Some(three));
+
+    /* The freed node is reused, but the old handle stays stale */
+    let five = list.push_back(5).unwrap();
+    assert_eq!(five.index(), two.index());
+    assert_eq!(list.get(two), None);
+    assert_eq!(contents(&list), [1, 3, 4, 5]);
+
+    assert_eq!(list.pop_back(), Some(5));
+    assert_eq!(list.pop_front(), Some(1));
+    *list.get_mut(three).unwrap() *= 10;
+    assert_eq!(list.front(), Some(&30));
+    assert_eq!(list.back(), Some(&4));
+
+    list.clear();
+    assert!(list.is_empty());
+    assert!(!list.contains(three));
+    assert_eq!(list.front_handle(), None);
+}
+
+#[test]
+fn move_and_iterate() {
+    let mut list = StackLinkedList::<i32, 5>::new();
+    let handles = (0..5)
+        .map(|i| list.push_back(i).unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(list.move_to_front(handles[3]));
+    assert!(list.move_to_back(handles[0]));
+    assert!(list.move_to_front(handles[3]));
+    assert_eq!(contents(&list), [3, 1, 2, 4, 0]);
+
+    let rev = list.iter().rev().map(|(_, v)| *v).collect::<Vec<_>>();
+    assert_eq!(rev, [0, 4, 2, 1, 3]);
+
+    let mut it = list.iter();
+    assert_eq!(it.next().map(|(h, _)| h), Some(handles[3]));
+    assert_eq!(it.next_back().map(|(h, _)| h), Some(handles[0]));
+    assert_eq!(it.len(), 3);
+
+    list.remove(handles[1]);
+    assert!(!list.move_to_back(handles[1]));
+    assert_eq!(contents(&list), [3, 2, 4, 0]);
+}
@@ -2,10 +2,16 @@
 
 echo "Linting..."
 
-((cargo --color=always test --all --all-features && \
+# The nightly only features are tested with cargo +nightly
+STABLE_FEATURES=overflow-hook,high-water-mark,poison,varint
+NIGHTLY_FEATURES=may-dangle,portable-simd
+
+((cargo --color=always test --all --features $STABLE_FEATURES && \
+cargo +nightly --color=always test --all --features $STABLE_FEATURES,$NIGHTLY_FEATURES && \
 cargo +nightly --color=always fmt --all -- --check && \
 cargo --color=always clippy -- -D warnings && \
-cargo --color=always doc --workspace --all-features --no-deps --document-private-items) &>/tmp/lint.txt ) || (
+cargo --color=always doc --workspace --features $STABLE_FEATURES --no-deps --document-private-items && \
+cargo +nightly --color=always doc --workspace --features $STABLE_FEATURES,$NIGHTLY_FEATURES --no-deps --document-private-items) &>/tmp/lint.txt ) || (
     cat /tmp/lint.txt
     echo "YOU CAN'T COMMIT THIS GARBAGE!"
     exit 1
@@ -0,0 +1,26 @@
+use stack_vector::StackLruCache;
+
+#[test]
+fn eviction_order() {
+    let mut cache = StackLruCache::<i32, i32, 3>::new();
+    assert_eq!(cache.put(1, 10), None);
+    assert_eq!(cache.put(2, 20), None);
+    assert_eq!(cache.put(3, 30), None);
+    assert!(cache.is_full());
+
+    assert_eq!(cache.get(&1), Some(&10));
+    assert_eq!(cache.peek(&2), Some(&20));
+    assert_eq!(cache.put(4, 40), Some((2, 20)));
+
+    *cache.get_mut(&3).unwrap() += 1;
+    assert_eq!(cache.put(1, 11), Some((1, 10)));
+
+    assert!(cache.iter().eq([(&4, &40), (&3, &31), (&1, &11)]));
+
+    assert_eq!(cache.pop_lru(), Some((4, 40)));
+    assert_eq!(cache.remove(&1), Some(11));
+    assert_eq!(cache.len(), 1);
+
+    let mut empty = StackLruCache::<i32, i32, 0>::new();
+    assert_eq!(empty.put(1, 1), Some((1, 1)));
+}
@@ -0,0 +1,61 @@
+use stack_vector::map::Entry;
+use stack_vector::{CapacityError, StackMap};
+
+#[test]
+fn entry() {
+    let mut map = StackMap::<i32, i32, 2>::new();
+
+    assert_eq!(map.entry(1).or_insert(10), Ok(&mut 10));
+    assert_eq!(
+        map.entry(1).and_modify(|v| *v += 1).or_insert(0),
+        Ok(&mut 11)
+    );
+    assert_eq!(map.entry(2).or_insert_with(|| 20), Ok(&mut 20));
+
+    assert!(map.is_full());
+    assert_eq!(map.entry(3).or_insert(30), Err(CapacityError));
+    assert_eq!(
+        map.entry(3).or_insert_with(|| unreachable!()),
+        Err(CapacityError)
+    );
+    assert_eq!(map.entry(2).or_insert(0), Ok(&mut 20));
+
+    match map.entry(3) {
+        Entry::Vacant(e) => assert_eq!(e.insert(30), Err((3, 30))),
+        Entry::Occupied(_) => unreachable!(),
+    }
+
+    match map.entry(1) {
+        Entry::Occupied(e) => assert_eq!(e.remove(), 11),
+        Entry::Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map.len(), 1);
+    assert!(!map.contains_key(&1));
+}
+
+#[test]
+fn iteration() {
+    let mut map = StackMap::<char, i32, 4>::new();
+    for (i, c) in "abcd".chars().enumerate() {
+        map.insert(c, i as i32).unwrap();
+    }
+
+    assert!(
+        map.iter()
+            .eq([(&'a', &0), (&'b', &1), (&'c', &2), (&'d', &3)])
+    );
+    assert!(map.keys().rev().eq(&['d', 'c', 'b', 'a']));
+
+    for (k, v) in map.iter_mut() {
+        if *k != 'a' {
+            *v *= 10;
+        }
+    }
+    map.values_mut().for_each(|v| *v += 1);
+    assert!(map.values().eq(&[1, 11, 21, 31]));
+
+    assert_eq!(map.retain(|_, v| *v > 15), 2);
+    assert_eq!(map.get(&'c'), Some(&21));
+    assert!(!map.contains_key(&'b'));
+    assert_eq!(map.iter().len(), 2);
+}
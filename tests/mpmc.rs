@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use stack_vector::mpmc::Queue;
+
+#[test]
+fn fifo_order() {
+    let q = Queue::<i32, 3>::new();
+    assert!(q.is_empty());
+
+    for round in 0..5 {
+        for i in 0..3 {
+            q.enqueue(round * 10 + i).unwrap();
+        }
+        assert!(q.is_full());
+        assert_eq!(q.enqueue(-1), Err(-1));
+        for i in 0..3 {
+            assert_eq!(q.dequeue(), Some(round * 10 + i));
+        }
+        assert_eq!(q.dequeue(), None);
+    }
+
+    let empty = Queue::<i32, 0>::new();
+    assert_eq!(empty.enqueue(1), Err(1));
+    assert_eq!(empty.dequeue(), None);
+}
+
+#[test]
+fn many_threads() {
+    const PER_PRODUCER: usize = 5_000;
+    let q = Queue::<usize, 16>::new();
+
+    let mut received = std::thread::scope(|s| {
+        for t in 0..4 {
+            let q = &q;
+            s.spawn(move || {
+                for i in 0..PER_PRODUCER {
+                    while q.enqueue(t * PER_PRODUCER + i).is_err() {}
+                }
+            });
+        }
+
+        let consumers = (0..4)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut got = Vec::new();
+                    let mut last = [None; 4];
+                    while got.len() < PER_PRODUCER {
+                        if let Some(n) = q.dequeue() {
+                            /* Elements from the same producer come out in order */
+                            let (t, i) = (n / PER_PRODUCER, n % PER_PRODUCER);
+                            assert!(last[t].is_none_or(|l| l < i));
+                            last[t] = Some(i);
+                            got.push(n);
+                        }
+                    }
+                    got
+                })
+            })
+            .collect::<Vec<_>>();
+        consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    received.sort();
+    assert!(received.into_iter().eq(0..4 * PER_PRODUCER));
+    assert!(q.is_empty());
+}
+
+#[test]
+fn drop_remaining() {
+    let rc = Rc::new(());
+    {
+        let q = Queue::<Rc<()>, 4>::new();
+        for _ in 0..3 {
+            q.enqueue(Rc::clone(&rc)).unwrap();
+        }
+        q.dequeue();
+        assert_eq!(Rc::strong_count(&rc), 3);
+    }
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,45 @@
+use stack_vector::StackPacketBuf;
+use stack_vector::bytes::UnderrunError;
+
+#[test]
+fn headroom_and_tailroom() {
+    let mut pkt = StackPacketBuf::<8>::with_headroom(3);
+    assert!(pkt.is_empty());
+    assert_eq!((pkt.headroom(), pkt.tailroom()), (3, 5));
+
+    pkt.push_back_slice(b"data").unwrap();
+    assert!(pkt.push_back_slice(b"xy").is_err());
+    pkt.push_back_slice(b"!").unwrap();
+    assert_eq!(pkt.tailroom(), 0);
+
+    assert!(pkt.push_front_slice(b"abcd").is_err());
+    assert_eq!(pkt.as_slice(), b"data!");
+    pkt.push_front_slice(b"ab").unwrap();
+    pkt.push_front_slice(b"c").unwrap();
+    assert_eq!(pkt.headroom(), 0);
+    assert_eq!(pkt.as_slice(), b"cabdata!");
+    assert_eq!(pkt.len(), pkt.capacity());
+
+    pkt.as_slice_mut()[0] = b'C';
+    assert_eq!(pkt.strip_front(3), Ok(&b"Cab"[..]));
+    assert_eq!(pkt.strip_back(1), Ok(&b"!"[..]));
+    assert_eq!(pkt.strip_back(5), Err(UnderrunError));
+    assert_eq!(pkt.as_slice(), b"data");
+    assert_eq!((pkt.headroom(), pkt.tailroom()), (3, 1));
+
+    pkt.reset(8);
+    assert!(pkt.is_empty());
+    assert!(pkt.push_back_slice(b"x").is_err());
+    pkt.push_front_slice(b"x").unwrap();
+    assert_eq!(pkt, {
+        let mut other = StackPacketBuf::new();
+        other.push_back_slice(b"x").unwrap();
+        other
+    });
+}
+
+#[test]
+#[should_panic(expected = "Headroom exceeds the capacity of the StackPacketBuf")]
+fn headroom_too_big() {
+    StackPacketBuf::<4>::with_headroom(5);
+}
@@ -0,0 +1,29 @@
+use std::rc::Rc;
+
+use stack_vector::StackPool;
+
+#[test]
+fn recycle() {
+    let rc = Rc::new(());
+    let pool = StackPool::<Rc<()>, 3>::new();
+
+    let a = pool.alloc(rc.clone()).unwrap();
+    let b = pool.alloc(rc.clone()).unwrap();
+    let c = pool.alloc(rc.clone()).unwrap();
+    assert!(pool.alloc(rc.clone()).is_err());
+    assert_eq!(Rc::strong_count(&rc), 4);
+
+    drop(b);
+    assert_eq!(Rc::strong_count(&rc), 3);
+    assert_eq!(pool.available(), 1);
+
+    let d = pool.alloc(rc.clone()).unwrap();
+    let inner = c.into_inner();
+    assert_eq!(pool.in_use(), 2);
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(inner);
+
+    drop((a, d));
+    assert_eq!(pool.available(), 3);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,27 @@
+use stack_vector::StackSet;
+
+#[test]
+fn set_operations() {
+    let mut a = StackSet::<u32, 5>::new();
+    for i in [1, 2, 3, 2, 1] {
+        a.insert(i).unwrap();
+    }
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.insert(3), Ok(false));
+
+    let mut b = StackSet::<u32, 3>::new();
+    for i in [3, 4, 5] {
+        assert_eq!(b.insert(i), Ok(true));
+    }
+    assert_eq!(b.insert(6), Err(6));
+
+    assert!(a.union(&b).copied().eq([1, 2, 3, 4, 5]));
+    assert!(a.intersection(&b).copied().eq([3]));
+    assert!(a.difference(&b).copied().eq([1, 2]));
+    assert!(b.difference(&a).copied().eq([4, 5]));
+
+    assert!(a.remove(&2));
+    assert!(!a.remove(&2));
+    assert!(!a.contains(&2));
+    assert_eq!(a.as_slice(), &[1, 3]);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "portable-simd")]
+
+use stack_vector::StackVec;
+
+#[test]
+fn integers() {
+    let sv = StackVec::<i32, 32>::from_fn_n(21, |i| i as i32 - 10);
+    assert_eq!(sv.sum(), 0);
+    assert_eq!(sv.min(), Some(-10));
+    assert_eq!(sv.max(), Some(10));
+    assert_eq!(sv.dot(&[2; 21]), 0);
+
+    let ones = StackVec::<u8, 300>::from_element_n(1, 300);
+    assert_eq!(ones.sum(), 300u32 as u8);
+    assert_eq!(ones.dot(ones.as_slice()), 300u32 as u8);
+
+    let empty = StackVec::<u64, 8>::new();
+    assert_eq!(empty.sum(), 0);
+    assert_eq!(empty.min(), None);
+    assert_eq!(empty.max(), None);
+}
+
+#[test]
+fn floats() {
+    let sv = StackVec::<f32, 16>::from_fn_n(11, |i| i as f32 * 0.5);
+    assert_eq!(sv.sum(), 27.5);
+    assert_eq!(sv.min(), Some(0.0));
+    assert_eq!(sv.max(), Some(5.0));
+
+    let a = StackVec::<f64, 10>::from_array([1.0; 10]);
+    let b = [0.5; 10];
+    assert_eq!(a.dot(&b), 5.0);
+}
+
+#[test]
+#[should_panic]
+fn dot_length_mismatch() {
+    let a = StackVec::<i32, 4>::from_array([1, 2, 3, 4]);
+    a.dot(&[1, 2]);
+}
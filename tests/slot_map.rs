@@ -0,0 +1,33 @@
+use stack_vector::StackSlotMap;
+
+#[test]
+fn stale_keys() {
+    let mut sm = StackSlotMap::<i32, 3>::new();
+    let a = sm.insert(1).unwrap();
+    let b = sm.insert(2).unwrap();
+    let c = sm.insert(3).unwrap();
+    assert!(sm.is_full());
+    assert_eq!(sm.insert(4), Err(4));
+
+    assert_eq!(sm.remove(b), Some(2));
+    assert_eq!(sm.remove(b), None);
+    assert_eq!(sm.get(b), None);
+
+    let d = sm.insert(4).unwrap();
+    assert_eq!(d.index(), b.index());
+    assert_ne!(d, b);
+    assert_eq!(sm.get(b), None);
+
+    *sm.get_mut(d).unwrap() *= 10;
+    assert!(sm.iter().map(|(_, v)| *v).eq([1, 40, 3]));
+    assert_eq!(sm.iter().len(), 3);
+
+    sm.clear();
+    assert!(sm.is_empty());
+    for k in [a, c, d] {
+        assert!(!sm.contains_key(k));
+    }
+
+    let e = sm.insert(5).unwrap();
+    assert_eq!(sm.get(e), Some(&5));
+}
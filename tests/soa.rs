@@ -0,0 +1,111 @@
+stack_vector::stack_soa! {
+    struct Entities[3] {
+        id: u32,
+        name: String,
+    }
+}
+
+/* A field can be called length, since the
+ * container doesn't have a hidden field for it */
+stack_vector::stack_soa! {
+    struct Segments[2] {
+        start: u16,
+        length: u16,
+    }
+}
+
+#[test]
+fn field_named_length() {
+    let mut s = Segments::new();
+    s.push(0, 10).unwrap();
+    s.push(10, 5).unwrap();
+    assert_eq!(s.length(), &[10, 5]);
+    assert_eq!(s.len(), 2);
+}
+
+#[test]
+fn push_remove_iter() {
+    let mut e = Entities::new();
+    assert!(e.is_empty());
+    assert_eq!(e.capacity(), 3);
+
+    e.push(1, "one".into()).unwrap();
+    e.push(2, "two".into()).unwrap();
+    e.push(3, "three".into()).unwrap();
+    assert!(e.is_full());
+    assert_eq!(e.push(4, "four".into()), Err((4, "four".into())));
+
+    assert_eq!(e.id(), &[1, 2, 3]);
+    assert_eq!(e.get(1), Some((&2, &"two".to_string())));
+    assert_eq!(e.get(3), None);
+
+    for (id, name) in e.iter_mut() {
+        *id *= 10;
+        name.push('!');
+    }
+    let collected = e
+        .iter()
+        .map(|(id, name)| format!("{id}{name}"))
+        .collect::<Vec<_>>();
+    assert_eq!(collected, ["10one!", "20two!", "30three!"]);
+
+    assert_eq!(e.remove(1), (20, "two!".into()));
+    assert_eq!(e.pop(), Some((30, "three!".into())));
+    assert_eq!(e.len(), 1);
+    assert_eq!(e.name(), &["one!"]);
+
+    let (ids, _) = e.columns_mut();
+    ids[0] = 7;
+    assert_eq!(e.id(), &[7]);
+
+    e.clear();
+    assert!(e.is_empty());
+    assert_eq!(e.pop(), None);
+}
+
+#[test]
+#[should_panic]
+fn remove_out_of_bounds() {
+    let mut e = Entities::default();
+    e.remove(0);
+}
+
+#[derive(Debug)]
+struct PanicOnDrop(bool);
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        if self.0 {
+            panic!("boom");
+        }
+    }
+}
+
+stack_vector::stack_soa! {
+    struct Fallible[2] {
+        first: PanicOnDrop,
+        second: u8,
+        third: String,
+    }
+}
+
+#[test]
+fn clear_with_panicking_drop() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let mut f = Fallible::new();
+    f.push(PanicOnDrop(false), 1, "a".into()).unwrap();
+    f.push(PanicOnDrop(true), 2, "b".into()).unwrap();
+
+    let res = catch_unwind(AssertUnwindSafe(|| f.clear()));
+    assert!(res.is_err());
+
+    /* Every field was cleared, so the lengths still match */
+    assert!(f.is_empty());
+    assert!(f.second().is_empty());
+    assert!(f.third().is_empty());
+    f.push(PanicOnDrop(false), 3, "c".into()).unwrap();
+    f.push(PanicOnDrop(false), 4, "d".into()).unwrap();
+    assert!(f.push(PanicOnDrop(false), 5, "e".into()).is_err());
+    assert_eq!(f.third(), &["c", "d"]);
+}
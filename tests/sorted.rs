@@ -0,0 +1,178 @@
+use std::rc::Rc;
+
+use stack_vector::StackVec;
+
+#[test]
+fn merge_sorted() {
+    let mut sv = StackVec::<(i32, &str), 8>::new();
+    sv.extend_from_iter([(1, "a"), (3, "a"), (3, "b"), (7, "a")]);
+
+    sv.merge_sorted([(0, "o"), (3, "o"), (8, "o")]).unwrap();
+    assert_eq!(
+        sv.as_slice(),
+        &[
+            (0, "o"),
+            (1, "a"),
+            (3, "a"),
+            (3, "b"),
+            (3, "o"),
+            (7, "a"),
+            (8, "o")
+        ]
+    );
+
+    assert!(sv.merge_sorted([(0, "x"), (9, "x")]).is_err());
+    assert_eq!(sv.len(), 7);
+
+    let mut empty = StackVec::<i32, 3>::new();
+    empty.merge_sorted([1, 2]).unwrap();
+    empty.merge_sorted([]).unwrap();
+    assert_eq!(empty.as_slice(), &[1, 2]);
+}
+
+/// An iterator that yields less elements than it reports
+struct Liar(std::vec::IntoIter<Rc<i32>>);
+
+impl Iterator for Liar {
+    type Item = Rc<i32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len() + 2, Some(self.0.len() + 2))
+    }
+}
+
+impl DoubleEndedIterator for Liar {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl ExactSizeIterator for Liar {}
+
+#[test]
+fn merge_sorted_with_short_iterator() {
+    let mut sv = StackVec::<Rc<i32>, 8>::new();
+    sv.extend_from_iter([1, 4].map(Rc::new));
+
+    sv.merge_sorted(Liar(vec![Rc::new(2), Rc::new(5)].into_iter()))
+        .unwrap();
+    assert!(sv.iter().map(|rc| **rc).eq([1, 2, 4, 5]));
+}
+
+#[test]
+fn merge_sorted_into() {
+    let a = StackVec::from_array([1, 1, 4]);
+    let mut out = StackVec::<i32, 6>::new();
+
+    a.merge_sorted_into(&[0, 1, 5], &mut out).unwrap();
+    assert_eq!(out.as_slice(), &[0, 1, 1, 1, 4, 5]);
+
+    out.clear();
+    assert!(a.merge_sorted_into(&[0; 4], &mut out).is_err());
+    assert!(out.is_empty());
+}
+
+#[test]
+fn set_operations() {
+    let a = StackVec::from_array([1, 3, 5, 7]);
+    let b = [2, 3, 4, 7, 9];
+    let mut out = StackVec::<i32, 8>::new();
+
+    a.intersection_with(&b, &mut out).unwrap();
+    assert_eq!(out.as_slice(), &[3, 7]);
+
+    out.clear();
+    a.union_into(&b, &mut out).unwrap();
+    assert_eq!(out.as_slice(), &[1, 2, 3, 4, 5, 7, 9]);
+
+    out.clear();
+    a.difference_into(&b, &mut out).unwrap();
+    assert_eq!(out.as_slice(), &[1, 5]);
+
+    let mut small = StackVec::<i32, 6>::new();
+    small.push(-1);
+    assert!(a.union_into(&b, &mut small).is_err());
+    assert_eq!(small.as_slice(), &[-1]);
+}
+
+/// A simple xorshift generator, to get reproducible inputs
+fn pseudo_random(n: usize, seed: u32) -> impl Iterator<Item = u32> {
+    let mut x = seed;
+    (0..n).map(move |_| {
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        x
+    })
+}
+
+fn check_stable_sort<const CAP: usize>(len: usize) {
+    let mut sv = StackVec::<(u32, usize), CAP>::new();
+    sv.extend_from_iter(pseudo_random(len, 7).map(|x| x % 10).zip(0..));
+
+    let mut expected = sv.to_vec();
+    expected.sort_by_key(|(k, _)| *k);
+
+    sv.sort_stable_by_key(|(k, _)| *k);
+    assert_eq!(sv.as_slice(), expected.as_slice());
+}
+
+#[test]
+fn sort_stable() {
+    /* With enough spare capacity for the merge buffer */
+    check_stable_sort::<200>(100);
+    /* Merging in place */
+    check_stable_sort::<100>(100);
+    check_stable_sort::<130>(100);
+    check_stable_sort::<5>(5);
+    check_stable_sort::<1>(0);
+
+    let mut sv = StackVec::<u32, 64>::new();
+    sv.extend_from_iter(pseudo_random(64, 3));
+    sv.sort_stable();
+    assert!(sv.is_sorted());
+}
+
+#[test]
+fn sort_stable_panic() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let rc = Rc::new(());
+    for cap_used in [40, 80] {
+        let mut sv = StackVec::<(u32, Rc<()>), 80>::new();
+        sv.extend_from_iter(pseudo_random(cap_used, 11).map(|x| (x, rc.clone())));
+
+        let mut comparisons = 0;
+        let res = catch_unwind(AssertUnwindSafe(|| {
+            sv.sort_stable_by(|a, b| {
+                comparisons += 1;
+                assert!(comparisons < 150);
+                a.0.cmp(&b.0)
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(sv.len(), cap_used);
+        assert_eq!(Rc::strong_count(&rc), cap_used + 1);
+    }
+}
+
+#[test]
+fn sort_by_cached_key() {
+    let mut calls = 0;
+    let mut sv = StackVec::<(u32, usize), 50>::new();
+    sv.extend_from_iter(pseudo_random(50, 5).map(|x| x % 7).zip(0..));
+
+    let mut expected = sv.to_vec();
+    expected.sort_by_key(|(k, _)| *k);
+
+    sv.sort_by_cached_key(|(k, _)| {
+        calls += 1;
+        *k
+    });
+    assert_eq!(calls, 50);
+    assert_eq!(sv.as_slice(), expected.as_slice());
+}
@@ -0,0 +1,43 @@
+use stack_vector::spsc::Queue;
+
+#[test]
+fn fifo_order() {
+    let mut q = Queue::<i32, 3>::new();
+    assert!(q.is_empty());
+
+    for round in 0..5 {
+        for i in 0..3 {
+            q.enqueue(round * 10 + i).unwrap();
+        }
+        assert!(q.is_full());
+        assert_eq!(q.enqueue(-1), Err(-1));
+        for i in 0..3 {
+            assert_eq!(q.dequeue(), Some(round * 10 + i));
+        }
+        assert_eq!(q.dequeue(), None);
+    }
+}
+
+#[test]
+fn split_threads() {
+    const N: u32 = 10_000;
+    let mut q = Queue::<u32, 8>::new();
+    let (mut tx, mut rx) = q.split();
+
+    std::thread::scope(|s| {
+        s.spawn(move || {
+            for i in 0..N {
+                while tx.enqueue(i).is_err() {}
+            }
+        });
+
+        let mut expected = 0;
+        while expected < N {
+            if let Some(i) = rx.dequeue() {
+                assert_eq!(i, expected);
+                expected += 1;
+            }
+        }
+        assert!(rx.peek().is_none());
+    });
+}
@@ -0,0 +1,106 @@
+use stack_vector::{StackString, StackVec};
+
+#[test]
+fn push_and_pop() {
+    let mut s = StackString::<6>::new();
+    s.push_str("añb");
+    assert_eq!(s.len(), 4);
+    assert_eq!(s.try_push('€'), Err('€'));
+    s.push('c');
+    assert_eq!(s, "añbc");
+
+    assert_eq!(s.pop(), Some('c'));
+    assert_eq!(s.pop(), Some('b'));
+    assert_eq!(s.pop(), Some('ñ'));
+    assert_eq!(s.as_str(), "a");
+}
+
+#[test]
+fn drain() {
+    let mut s = StackString::<16>::try_from("añb€c").unwrap();
+
+    let mut d = s.drain(1..7);
+    assert_eq!(d.next(), Some('ñ'));
+    assert_eq!(d.next_back(), Some('€'));
+    assert_eq!(d.as_str(), "b");
+    drop(d);
+    assert_eq!(s, "ac");
+
+    let mut s = StackString::<16>::try_from("añb€c").unwrap();
+    assert!(s.drain(..).rev().eq("añb€c".chars().rev()));
+    assert!(s.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Attempt to push beyond the capacity of the string")]
+fn push_str_full() {
+    let mut s = StackString::<2>::new();
+    s.push_str("abc");
+}
+
+#[test]
+#[should_panic(expected = "Range 1..2 is not on a char boundary")]
+fn drain_not_on_char_boundary() {
+    let mut s = StackString::<8>::try_from("ñ").unwrap();
+    s.drain(1..);
+}
+
+#[test]
+fn extend() {
+    let mut s = StackString::<8>::new();
+    s.extend("abc".chars().rev());
+    s.extend(["d", "ef"]);
+    assert_eq!(s, "cbadef");
+
+    assert!(s.try_extend_strs(["g", "hi"]).is_err());
+    assert_eq!(s, "cbadefg");
+    assert!(s.try_extend_chars(['h']).is_ok());
+    assert!(s.try_extend_chars(['i']).is_err());
+    assert!(s.is_full());
+}
+
+#[test]
+fn truncating_writer() {
+    use stack_vector::{StackVec, TruncatingWriter};
+    use std::fmt::Write;
+
+    let mut s = StackString::<6>::new();
+    let mut w = TruncatingWriter::for_string(&mut s);
+    let euros = "€€";
+    write!(w, "ab{euros}").unwrap();
+    assert_eq!(w.dropped(), 3);
+    /* Nothing is written after a truncation, even if it fits */
+    w.write_char('c').unwrap();
+    assert_eq!(w.dropped(), 4);
+    assert!(w.is_truncated());
+    assert_eq!(s, "ab€");
+
+    let mut buf = StackVec::<u8, 4>::new();
+    let mut w = TruncatingWriter::new(&mut buf);
+    write!(w, "{}", 12).unwrap();
+    assert!(!w.is_truncated());
+    write!(w, "{}", 345).unwrap();
+    assert_eq!(w.dropped(), 1);
+    assert_eq!(buf.as_slice(), b"1234");
+}
+
+#[test]
+fn bytes_conversions() {
+    let mut bytes = StackVec::<u8, 8>::new();
+    bytes.extend_from_iter("añb".bytes());
+    let mut s = StackString::from_utf8(bytes).unwrap();
+    assert_eq!(s, "añb");
+
+    unsafe { s.as_bytes_vec_mut().extend_from_iter(*b"cd") };
+    assert_eq!(s, "añbcd");
+
+    let bytes: StackVec<u8, 8> = s.into();
+    assert_eq!(bytes.as_slice(), "añbcd".as_bytes());
+
+    let mut invalid = StackVec::<u8, 8>::new();
+    invalid.extend_from_iter([b'a', 0xFF, b'b']);
+    let err = StackString::from_utf8(invalid).unwrap_err();
+    assert_eq!(err.utf8_error().valid_up_to(), 1);
+    assert_eq!(err.as_bytes(), &[b'a', 0xFF, b'b']);
+    assert_eq!(err.into_bytes().len(), 3);
+}
@@ -1,6 +1,6 @@
 use std::ops::Deref;
 
-use stack_vector::StackVec;
+use stack_vector::{CapacityError, NonEmptyStackVec, StackCow, StackVec, StackVecBuilder};
 
 #[test]
 fn push() {
@@ -26,6 +26,223 @@ fn push() {
     assert_eq!(sv.as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
 }
 
+#[test]
+fn extend_from_array() {
+    let mut sv = StackVec::<String, 4>::new();
+    sv.extend_from_array(["a".into(), "b".into()]);
+    sv.extend_from_array([]);
+
+    let res = sv.try_extend_from_array(["c", "d", "e"].map(String::from));
+    assert_eq!(res.unwrap_err(), ["c", "d", "e"]);
+
+    sv.extend_from_array(["c", "d"].map(String::from));
+    assert_eq!(sv.as_slice(), &["a", "b", "c", "d"]);
+}
+
+#[test]
+fn extend_from_slices() {
+    let mut sv = StackVec::<i32, 6>::new();
+    sv.extend_from_slices(&[&[1, 2], &[], &[3]]).unwrap();
+    sv.extend_from_slices(&[]).unwrap();
+    assert_eq!(sv.as_slice(), &[1, 2, 3]);
+
+    assert_eq!(
+        sv.extend_from_slices(&[&[4, 5], &[6, 7]]),
+        Err(CapacityError)
+    );
+    assert_eq!(sv.as_slice(), &[1, 2, 3]);
+
+    sv.extend_from_slices(&[&[4], &[5, 6]]).unwrap();
+    assert_eq!(sv.as_slice(), &[1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn swap_with() {
+    let mut a = StackVec::<String, 3>::new();
+    let mut b = StackVec::<String, 5>::new();
+    a.extend_from_iter(["a1", "a2"].map(String::from));
+    b.extend_from_iter(["b1", "b2", "b3"].map(String::from));
+
+    a.swap_with(&mut b).unwrap();
+    assert_eq!(a.as_slice(), &["b1", "b2", "b3"]);
+    assert_eq!(b.as_slice(), &["a1", "a2"]);
+
+    b.swap_with(&mut a).unwrap();
+    assert_eq!(a.as_slice(), &["a1", "a2"]);
+    assert_eq!(b.as_slice(), &["b1", "b2", "b3"]);
+
+    b.push("b4".into());
+    assert_eq!(a.swap_with(&mut b), Err(CapacityError));
+    assert_eq!(b.swap_with(&mut a), Err(CapacityError));
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.len(), 4);
+
+    let mut empty = StackVec::<String, 0>::new();
+    a.clear();
+    a.swap_with(&mut empty).unwrap();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn resize_default() {
+    let mut sv = StackVec::<String, 4>::new();
+    sv.push("a".into());
+
+    sv.resize_default(3).unwrap();
+    assert_eq!(sv.as_slice(), &["a", "", ""]);
+
+    assert_eq!(sv.resize_default(5), Err(CapacityError));
+    assert_eq!(sv.len(), 3);
+
+    sv.resize_default(1).unwrap();
+    assert_eq!(sv.as_slice(), &["a"]);
+
+    sv.resize_default(4).unwrap();
+    assert!(sv.is_full());
+}
+
+#[test]
+fn from_fn_n() {
+    let sv = StackVec::<String, 4>::from_fn_n(3, |i| i.to_string());
+    assert_eq!(sv.as_slice(), &["0", "1", "2"]);
+
+    let empty = StackVec::<String, 4>::from_fn_n(0, |_| unreachable!());
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn from_fn_n_beyond_capacity() {
+    StackVec::<i32, 2>::from_fn_n(3, |i| i as i32);
+}
+
+#[test]
+fn from_element_n() {
+    let sv = StackVec::<String, 4>::from_element_n("ab".into(), 2);
+    assert_eq!(sv.as_slice(), &["ab", "ab"]);
+    assert_eq!(sv.remaining_capacity(), 2);
+
+    let full = StackVec::<i32, 3>::from_element_n(7, 3);
+    assert_eq!(full.as_slice(), &[7, 7, 7]);
+
+    let empty = StackVec::<i32, 3>::from_element_n(7, 0);
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn from_element_n_beyond_capacity() {
+    StackVec::<i32, 2>::from_element_n(0, 3);
+}
+
+#[test]
+fn zeroed() {
+    let sv = unsafe { StackVec::<u64, 32>::zeroed() };
+    assert!(sv.is_full());
+    assert!(sv.iter().all(|&n| n == 0));
+
+    let mut sv = unsafe { StackVec::<f32, 8>::with_len_zeroed(3) };
+    assert_eq!(sv.as_slice(), &[0.0, 0.0, 0.0]);
+    sv.push(1.5);
+    assert_eq!(sv.len(), 4);
+
+    let empty = unsafe { StackVec::<u8, 8>::with_len_zeroed(0) };
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn repeat_slice() {
+    let sv = StackVec::<&str, 7>::repeat_slice(&["a", "b"]).unwrap();
+    assert_eq!(sv.as_slice(), &["a", "b", "a", "b", "a", "b"]);
+
+    let empty = StackVec::<i32, 4>::repeat_slice(&[]).unwrap();
+    assert!(empty.is_empty());
+    assert!(StackVec::<i32, 1>::repeat_slice(&[1, 2]).is_err());
+
+    let mut sv = StackVec::<i32, 6>::new();
+    sv.extend_repeating(&[1, 2], 2).unwrap();
+    sv.extend_repeating(&[3], 0).unwrap();
+    assert_eq!(sv.extend_repeating(&[5], 3), Err(CapacityError));
+    assert_eq!(sv.extend_repeating(&[5], usize::MAX), Err(CapacityError));
+    sv.extend_repeating(&[9], 2).unwrap();
+    assert_eq!(sv.as_slice(), &[1, 2, 1, 2, 9, 9]);
+}
+
+#[test]
+fn push_with() {
+    let mut sv = StackVec::<String, 2>::new();
+    sv.push("a".into());
+
+    let s = sv.push_with(|slot| slot.write("b".into())).unwrap();
+    s.push('c');
+    assert_eq!(sv.as_slice(), &["a", "bc"]);
+
+    assert!(sv.push_with(|slot| slot.write("d".into())).is_err());
+    assert_eq!(sv.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "must initialize the given slot")]
+fn push_with_other_reference_must_panic() {
+    let mut sv = StackVec::<u8, 2>::new();
+    let _ = sv.push_with(|_| Box::leak(Box::new(1)));
+}
+
+#[test]
+fn init_spare() {
+    let mut sv = StackVec::<String, 4>::new();
+    sv.push("a".into());
+
+    let new = sv.init_spare_with(2, |i| i.to_string()).unwrap();
+    new[0].push('!');
+    assert_eq!(sv.as_slice(), &["a", "0!", "1"]);
+    assert!(sv.init_spare_with(2, |_| String::new()).is_err());
+
+    let spare = sv.spare_capacity_mut();
+    assert_eq!(spare.len(), 1);
+    spare[0].write("b".into());
+    unsafe { sv.set_len(4) };
+    assert_eq!(sv.as_slice(), &["a", "0!", "1", "b"]);
+}
+
+#[test]
+fn init_spare_panic() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut sv = StackVec::<_, 4>::new();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        let _ = sv.init_spare_with(4, |i| {
+            assert!(i < 2);
+            rc.clone()
+        });
+    }));
+    assert!(res.is_err());
+    assert_eq!(sv.len(), 2);
+    drop(sv);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn capacity_assertions() {
+    const {
+        StackVec::<u8, 8>::assert_capacity_at_least::<8>();
+        StackVec::<u8, 8>::assert_capacity_at_most::<8>();
+    }
+    StackVec::<u8, 8>::assert_capacity_at_least::<0>();
+    StackVec::<u8, 8>::assert_capacity_at_most::<{ usize::MAX }>();
+}
+
+#[test]
+fn ensure_remaining() {
+    let mut sv = StackVec::<u8, 3>::new();
+    assert!(sv.ensure_remaining(3).is_ok());
+    sv.push(1);
+    assert_eq!(sv.ensure_remaining(3), Err(stack_vector::CapacityError));
+    assert!(sv.ensure_remaining(0).is_ok());
+}
+
 #[test]
 #[should_panic(expected = "Attemp to push beyond the capacity of the array")]
 fn out_of_bounds_must_panic() {
@@ -40,11 +257,38 @@ fn out_of_bounds_must_panic() {
 fn remove() {
     let mut sv = StackVec::from_array([1, 2, 3, 4, 5, 6]);
 
-    assert_eq!(sv.remove(1), Some(2));
-    assert_eq!(sv.remove(4), Some(6));
-    assert_eq!(sv.remove(0), Some(1));
+    assert_eq!(sv.remove(1), 2);
+    assert_eq!(sv.remove(4), 6);
+    assert_eq!(sv.remove(0), 1);
 
     assert_eq!(sv.deref(), &[3, 4, 5]);
+
+    assert_eq!(sv.try_remove(3), None);
+    assert_eq!(sv.try_remove(2), Some(5));
+    assert_eq!(sv.pop(), Some(4));
+    assert_eq!(sv.pop(), Some(3));
+    assert_eq!(sv.pop(), None);
+}
+
+#[test]
+#[should_panic(expected = "Index 3 out of bounds for a StackVec of length 3")]
+fn remove_out_of_bounds_must_panic() {
+    let mut sv = StackVec::from_array([1, 2, 3]);
+    sv.remove(3);
+}
+
+#[test]
+fn replace() {
+    let mut sv = StackVec::<String, 4>::new();
+    sv.extend_from_iter(["a", "b"].map(String::from));
+
+    assert_eq!(sv.replace(0, "c".into()).as_deref(), Some("a"));
+    assert_eq!(sv.replace(2, "d".into()), None);
+    assert_eq!(sv.as_slice(), &["c", "b"]);
+
+    assert_eq!(sv.take(1).as_deref(), Some("b"));
+    assert_eq!(sv.take(2), None);
+    assert_eq!(sv.as_slice(), &["c", ""]);
 }
 
 #[test]
@@ -64,6 +308,166 @@ fn constructors() {
     assert_eq!(sv.as_slice(), &[0, 0, 0, 0, 0]);
 }
 
+#[test]
+fn const_accessors() {
+    static EMPTY: StackVec<u8, 2> = StackVec::new();
+    static SV: StackVec<u8, 3> = StackVec::from_array([1, 2, 3]);
+    const FIRST: Option<&u8> = SV.first();
+    const LAST: Option<&u8> = SV.last();
+
+    assert_eq!(FIRST, Some(&1));
+    assert_eq!(LAST, Some(&3));
+    assert_eq!(SV.get(2), Some(&3));
+    assert_eq!(SV.get(3), None);
+    assert_eq!(EMPTY.first(), None);
+    assert_eq!(EMPTY.last(), None);
+}
+
+#[test]
+fn pointer_accessors() {
+    let mut sv = StackVec::<u16, 4>::new();
+    sv.extend_from_iter([1, 2]);
+
+    let range = sv.as_ptr_range();
+    assert_eq!(range.start, sv.as_ptr());
+    assert_eq!(range.end, sv.as_slice().as_ptr_range().end);
+
+    let range = sv.as_mut_ptr_range();
+    unsafe { range.start.write(5) };
+    assert_eq!(sv.as_non_null().as_ptr(), range.start);
+    assert_eq!(sv.as_slice(), &[5, 2]);
+}
+
+#[test]
+fn bounded_index() {
+    use stack_vector::Idx;
+
+    let mut sv = StackVec::<u32, 4>::new();
+    sv.extend_from_iter([1, 2]);
+
+    for i in sv.indices().collect::<Vec<_>>() {
+        *sv.get_bounded_mut(i).unwrap() *= 10;
+    }
+    assert_eq!(sv.as_slice(), &[10, 20]);
+
+    let out = Idx::<4>::new_wrapping(6);
+    assert_eq!(out.get(), 2);
+    assert_eq!(sv.get_bounded(out), None);
+    assert_eq!(Idx::<4>::new(4), None);
+    assert_eq!(Idx::<4>::all().len(), 4);
+
+    assert!(sv.as_array().is_none());
+    sv.extend_from_iter([3, 4]);
+    let arr = sv.as_array_mut().unwrap();
+    for i in Idx::all() {
+        arr[i] += 1;
+    }
+    assert_eq!(sv.as_array(), Some(&[11, 21, 4, 5]));
+
+    let grid = [[0u8; 3]; 2];
+    assert_eq!(
+        grid[Idx::<2>::new(1).unwrap()][Idx::<3>::new(2).unwrap()],
+        0
+    );
+}
+
+#[test]
+fn pinned_elements() {
+    use std::marker::PhantomPinned;
+    use std::pin::pin;
+
+    struct Node {
+        addr: usize,
+        _pin: PhantomPinned,
+    }
+
+    let mut sv = pin!(StackVec::<Node, 3>::new());
+    for _ in 0..3 {
+        let node = Node {
+            addr: 0,
+            _pin: PhantomPinned,
+        };
+        assert!(sv.as_mut().push_pinned(node).is_ok());
+    }
+
+    /* Each node records its own address, which must not change */
+    for node in sv.as_mut().iter_pin_mut() {
+        let node = unsafe { node.get_unchecked_mut() };
+        node.addr = node as *const Node as usize;
+    }
+    let second = sv.as_mut().get_pin_mut(1).unwrap();
+    assert_eq!(second.addr, &*second as *const Node as usize);
+    assert!(sv.as_mut().get_pin_mut(3).is_none());
+
+    sv.as_mut().clear_pinned();
+    assert!(sv.is_empty());
+}
+
+#[test]
+fn as_chunks() {
+    let mut sv = StackVec::<u8, 8>::new();
+    sv.extend_from_iter(0..7);
+
+    let (chunks, rest) = sv.as_chunks::<3>();
+    assert_eq!(chunks, &[[0, 1, 2], [3, 4, 5]]);
+    assert_eq!(rest, &[6]);
+
+    let (chunks, rest) = sv.as_chunks_mut::<4>();
+    chunks[0].reverse();
+    rest[0] = 9;
+    assert_eq!(sv.as_slice(), &[3, 2, 1, 0, 9, 5, 6]);
+
+    let (chunks, rest) = sv.as_chunks::<8>();
+    assert!(chunks.is_empty());
+    assert_eq!(rest.len(), 7);
+}
+
+#[test]
+fn fill_to_capacity() {
+    let mut sv = StackVec::<String, 4>::new();
+    sv.push("a".into());
+    sv.fill_to_capacity("b".into());
+    assert_eq!(sv.as_slice(), &["a", "b", "b", "b"]);
+    sv.fill_to_capacity("c".into());
+    assert!(sv.iter().all(|s| s != "c"));
+
+    let mut n = 0;
+    let mut sv = StackVec::<i32, 4>::new();
+    sv.push(0);
+    sv.fill_to_capacity_with(|| {
+        n += 1;
+        n
+    });
+    assert_eq!(sv.as_slice(), &[0, 1, 2, 3]);
+}
+
+#[test]
+fn fmt_write() {
+    use std::fmt::Write;
+
+    let mut buf = StackVec::<u8, 6>::new();
+    let c = 'ñ';
+    write!(buf, "{c}-").unwrap();
+    assert_eq!(buf.as_slice(), "ñ-".as_bytes());
+
+    assert!(buf.write_str("abcd").is_err());
+    buf.write_str("abc").unwrap();
+    assert!(buf.is_full());
+}
+
+#[test]
+fn display_separated() {
+    let mut sv = StackVec::<&str, 4>::new();
+    assert_eq!(sv.display_separated(", ").to_string(), "");
+
+    sv.push("a");
+    assert_eq!(sv.display_separated(", ").to_string(), "a");
+
+    sv.extend_from_iter(["b", "c"]);
+    assert_eq!(sv.display_separated(" -> ").to_string(), "a -> b -> c");
+    assert_eq!(format!("{:>2}", sv.display_separated("")), " a b c");
+}
+
 #[test]
 fn drain() {
     let mut sv = StackVec::<i32, 10>::from_array([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
@@ -75,3 +479,759 @@ fn drain() {
 
     assert_eq!(sv.len(), 7);
 }
+
+#[test]
+#[cfg(all(feature = "poison", debug_assertions))]
+fn poison_vacated_slots() {
+    use stack_vector::POISON_BYTE;
+
+    let poisoned = |sv: &StackVec<u32, 6>, i: usize| unsafe {
+        sv.as_ptr().add(i).cast::<[u8; 4]>().read() == [POISON_BYTE; 4]
+    };
+
+    let mut sv = StackVec::<u32, 6>::from_array([1, 2, 3, 4, 5, 6]);
+
+    sv.remove(0);
+    assert!(poisoned(&sv, 5));
+
+    sv.drain(1..3).for_each(drop);
+    assert_eq!(sv.as_slice(), &[2, 5, 6]);
+    assert!(poisoned(&sv, 3) && poisoned(&sv, 4));
+
+    sv.clear();
+    assert!((0..6).all(|i| poisoned(&sv, i)));
+}
+
+#[test]
+#[cfg(feature = "high-water-mark")]
+fn high_water_mark() {
+    let mut sv = StackVec::<i32, 10>::new();
+    assert_eq!(sv.high_water_mark(), 0);
+
+    sv.extend_from_iter(0..7);
+    sv.drain(2..);
+    sv.push(1);
+    assert_eq!(sv.high_water_mark(), 7);
+
+    sv.reset_high_water_mark();
+    assert_eq!(sv.high_water_mark(), 3);
+
+    let sv = StackVec::from_array([0; 4]);
+    assert_eq!(sv.high_water_mark(), 4);
+}
+
+#[test]
+#[cfg(feature = "may-dangle")]
+fn may_dangle() {
+    let mut sv = StackVec::<&String, 2>::new();
+    let s = String::from("a");
+    sv.push(&s);
+    /* Without the eyepatch, s would need to outlive sv */
+    drop(s);
+}
+
+#[test]
+fn drain_keep_rest() {
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter((0..8).map(|i| i.to_string()));
+
+    let mut d = sv.drain(2..6);
+    assert_eq!(d.next().as_deref(), Some("2"));
+    assert_eq!(d.next_back().as_deref(), Some("5"));
+    assert_eq!(d.as_slice(), &["3", "4"]);
+    d.keep_rest();
+
+    assert_eq!(sv.as_slice(), &["0", "1", "3", "4", "6", "7"]);
+
+    sv.drain(..).keep_rest();
+    assert_eq!(sv.len(), 6);
+}
+
+#[test]
+fn extract_if() {
+    let mut sv = StackVec::<String, 10>::new();
+    sv.extend_from_iter((0..10).map(|i| i.to_string()));
+
+    let mut it = sv.extract_if(|s| s.parse::<i32>().unwrap() % 3 == 0);
+    assert_eq!(it.next().as_deref(), Some("0"));
+    assert_eq!(it.next().as_deref(), Some("3"));
+    drop(it);
+    assert_eq!(sv.as_slice(), &["1", "2", "4", "5", "6", "7", "8", "9"]);
+
+    let removed = sv.extract_if(|s| s.len() == 1).count();
+    assert_eq!(removed, 8);
+    assert!(sv.is_empty());
+}
+
+#[test]
+fn retain() {
+    let mut sv = StackVec::<String, 10>::new();
+    sv.extend_from_iter((0..10).map(|i| i.to_string()));
+
+    assert_eq!(sv.retain(|s| s != "3"), 1);
+    assert_eq!(sv.retain(|s| s == "3"), 9);
+    assert!(sv.is_empty());
+
+    let mut sv = StackVec::from_array([1, 2, 3, 4]);
+    let removed = sv.retain_mut(|x| {
+        *x *= 10;
+        *x > 20
+    });
+    assert_eq!(removed, 2);
+    assert_eq!(sv.as_slice(), &[30, 40]);
+}
+
+#[test]
+fn retain_indexed() {
+    let mut sv = StackVec::from_array(["a", "b", "c", "d", "e"]);
+    let removed = sv.retain_indexed(|i, s| i == 0 || *s == "d");
+    assert_eq!(removed, 3);
+    assert_eq!(sv.as_slice(), &["a", "d"]);
+}
+
+#[test]
+fn retain_map() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter(["a", "bb", "c", "dd"].map(String::from));
+
+    let removed = sv.retain_map(|s| (s.len() == 1).then(|| s.repeat(3)));
+    assert_eq!(removed, 2);
+    assert_eq!(sv.as_slice(), &["aaa", "ccc"]);
+
+    sv.extend_from_iter(["e", "f"].map(String::from));
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        sv.retain_map(|s| {
+            assert_ne!(s, "e");
+            (s != "aaa").then_some(s)
+        })
+    }));
+    assert!(res.is_err());
+    /* The element passed to the function when it panicked is lost */
+    assert_eq!(sv.as_slice(), &["ccc", "f"]);
+}
+
+#[test]
+fn retain_swap() {
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter((0..8).map(|i| i.to_string()));
+
+    let removed = sv.retain_swap(|s| s.parse::<i32>().unwrap() % 3 != 0);
+    assert_eq!(removed, 3);
+    assert_eq!(sv.as_slice(), &["7", "1", "2", "5", "4"]);
+
+    assert_eq!(sv.retain_swap(|_| false), 5);
+    assert!(sv.is_empty());
+}
+
+#[test]
+fn dedup() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut sv = StackVec::<_, 8>::new();
+    sv.extend_from_iter([1, 1, 2, 2, 2, 3, 1, 1].map(|i| (i, rc.clone())));
+
+    sv.dedup_by_key(|(i, _)| *i);
+    assert!(sv.iter().map(|(i, _)| *i).eq([1, 2, 3, 1]));
+    assert_eq!(Rc::strong_count(&rc), 5);
+
+    let mut sv = StackVec::from_array(["b", "B", "a", "c", "A", "b"]);
+    sv.sort_unstable_and_dedup_by_key(|s| s.to_lowercase());
+    assert_eq!(sv.len(), 3);
+    assert!(sv.iter().map(|s| s.to_lowercase()).eq(["a", "b", "c"]));
+
+    let mut sv = StackVec::from_array([3, 1, 3, 2, 1]);
+    sv.sort_unstable_and_dedup();
+    assert_eq!(sv.as_slice(), &[1, 2, 3]);
+}
+
+#[test]
+fn dedup_panic() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter(["a", "a", "b", "b", "c"].map(String::from));
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        sv.dedup_by(|a, b| {
+            assert_ne!(a, "c");
+            a == b
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(sv.as_slice(), &["a", "b", "c"]);
+}
+
+#[test]
+fn filter_some() {
+    let mut sv = StackVec::<Option<String>, 4>::new();
+    sv.extend_from_iter([None, Some("a".into()), None, Some("b".into())]);
+    assert_eq!(sv.filter_some().as_slice(), &["a", "b"]);
+
+    let sv = StackVec::<Option<u8>, 2>::from_array([None, None]);
+    assert!(sv.filter_some().is_empty());
+}
+
+#[test]
+fn group_runs() {
+    let sv = StackVec::<&str, 8>::new();
+    assert!(sv.group_runs().is_empty());
+
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter(["a", "a", "b", "a", "a", "a"].map(String::from));
+    let runs = sv.group_runs();
+    assert_eq!(
+        runs.as_slice(),
+        &[("a".into(), 2), ("b".into(), 1), ("a".into(), 3)]
+    );
+}
+
+#[test]
+fn zip_with() {
+    let mut names = StackVec::<&str, 4>::new();
+    names.extend_from_iter(["a", "b"]);
+    let mut counts = StackVec::<usize, 4>::new();
+    counts.extend_from_iter([1, 3]);
+    let joined = names.zip_with(counts, |n, c| n.repeat(c));
+    assert_eq!(joined.as_slice(), &["a", "bbb"]);
+
+    let empty = StackVec::<i32, 4>::new();
+    assert!(
+        empty
+            .zip_with(StackVec::<i32, 4>::new(), |a, b| a * b)
+            .is_empty()
+    );
+}
+
+#[test]
+#[should_panic(expected = "Length mismatch between StackVecs (2 != 1)")]
+fn zip_with_mismatch() {
+    let a = StackVec::<i32, 4>::from_fn_n(2, |i| i as i32);
+    let b = StackVec::<i32, 4>::from_fn_n(1, |i| i as i32);
+    a.zip_with(b, |x, y| x + y);
+}
+
+#[test]
+fn scan_in_place() {
+    let mut sv = StackVec::<String, 4>::new();
+    sv.extend_from_iter(["a", "b", "c"].map(String::from));
+    sv.scan_in_place(String::from(">"), |acc, x| format!("{acc}{x}"));
+    assert_eq!(sv.as_slice(), &[">a", ">ab", ">abc"]);
+
+    let mut empty = StackVec::<i32, 4>::new();
+    empty.scan_in_place(1, |a, b| a + b);
+    empty.prefix_sums();
+    assert!(empty.is_empty());
+
+    let mut offsets = StackVec::<f32, 4>::from_array([0.5, 1.0, 1.5, 2.0]);
+    offsets.prefix_sums();
+    assert_eq!(offsets.as_slice(), &[0.5, 1.5, 3.0, 5.0]);
+}
+
+#[test]
+fn coalesce() {
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter(["a", "b", "-", "c", "-", "-", "d"].map(String::from));
+    sv.coalesce(|a, b| {
+        if a != "-" && b != "-" {
+            Ok(a + &b)
+        } else {
+            Err((a, b))
+        }
+    });
+    assert_eq!(sv.as_slice(), &["ab", "-", "c", "-", "-", "d"]);
+
+    sv.coalesce(|a, b| Ok(a + &b));
+    assert_eq!(sv.as_slice(), &["ab-c--d"]);
+}
+
+#[test]
+fn coalesce_panic() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut sv = StackVec::<Rc<()>, 8>::new();
+    sv.extend_from_iter((0..5).map(|_| rc.clone()));
+    let mut calls = 0;
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        sv.coalesce(|a, b| {
+            calls += 1;
+            if calls == 3 {
+                panic!("boom");
+            }
+            Err((a, b))
+        })
+    }));
+    assert!(res.is_err());
+    /* The two elements passed to the panicking call are dropped */
+    assert_eq!(sv.len(), 3);
+    assert_eq!(Rc::strong_count(&rc), 4);
+    sv.clear();
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn split_on() {
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter([",", "a", "b", ",", ",", "c"].map(String::from));
+    let pieces = sv
+        .split_on(|s| s == ",")
+        .map(|p| p.to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(pieces, [vec![], vec!["a", "b"], vec![], vec!["c"]]);
+
+    let last = sv.split_on(|s| s == ",").next_back().unwrap();
+    assert_eq!(last.as_slice(), &["c"]);
+
+    let empty = StackVec::<i32, 4>::new();
+    assert_eq!(empty.split_on(|&x| x == 0).count(), 1);
+}
+
+#[test]
+fn move_range() {
+    let mut sv = StackVec::<String, 8>::new();
+    sv.extend_from_iter(["a", "b", "c", "d", "e", "f"].map(String::from));
+
+    sv.move_range(4..6, 1);
+    assert_eq!(sv.as_slice(), &["a", "e", "f", "b", "c", "d"]);
+
+    sv.move_range(0..1, 5);
+    assert_eq!(sv.as_slice(), &["e", "f", "b", "c", "d", "a"]);
+
+    sv.move_range(2..2, 0);
+    sv.move_range(.., 0);
+    sv.move_range(3..4, 3);
+    assert_eq!(sv.as_slice(), &["e", "f", "b", "c", "d", "a"]);
+}
+
+#[test]
+#[should_panic(expected = "Index 4 out of bounds for a StackVec of length 3")]
+fn move_range_out_of_bounds() {
+    let mut sv = StackVec::from_array([1, 2, 3, 4, 5]);
+    sv.move_range(0..2, 4);
+}
+
+#[test]
+fn truncate_with() {
+    let mut sv = StackVec::<String, 6>::new();
+    sv.extend_from_iter(["a", "b", "c", "d"].map(String::from));
+
+    let mut removed = String::new();
+    sv.truncate_with(5, |s| removed.push_str(&s));
+    assert!(removed.is_empty());
+
+    sv.truncate_with(1, |s| removed.push_str(&s));
+    assert_eq!(removed, "bcd");
+    assert_eq!(sv.as_slice(), &["a"]);
+
+    sv.clear_with(|s| removed.push_str(&s));
+    assert_eq!(removed, "bcda");
+    assert!(sv.is_empty());
+}
+
+#[test]
+fn split() {
+    let mut sv = StackVec::<String, 6>::new();
+    sv.extend_from_iter(["a", "b", "c", "d", "e"].map(String::from));
+
+    let head = sv.split_to(2);
+    assert_eq!(head.as_slice(), &["a", "b"]);
+    assert_eq!(sv.as_slice(), &["c", "d", "e"]);
+
+    let tail = sv.split_off(1);
+    assert_eq!(tail.as_slice(), &["d", "e"]);
+    assert_eq!(sv.as_slice(), &["c"]);
+
+    assert!(sv.split_to(0).is_empty());
+    assert!(sv.split_off(1).is_empty());
+    assert_eq!(sv.split_to(1).as_slice(), &["c"]);
+    assert!(sv.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Index 3 out of bounds for a StackVec of length 2")]
+fn split_to_out_of_bounds_must_panic() {
+    let mut sv = StackVec::from_array([1, 2]);
+    sv.split_to(3);
+}
+
+#[test]
+fn splice() {
+    let mut sv = StackVec::<String, 6>::new();
+    sv.extend_from_iter(["a", "b", "c", "d"].map(String::from));
+
+    let removed = sv.splice(1..2, ["x", "y"].map(String::from)).unwrap();
+    assert_eq!(removed.as_slice(), &["b"]);
+    assert_eq!(sv.as_slice(), &["a", "x", "y", "c", "d"]);
+
+    let removed = sv.splice(..3, []).unwrap();
+    assert_eq!(removed.as_slice(), &["a", "x", "y"]);
+    assert_eq!(sv.as_slice(), &["c", "d"]);
+
+    let removed = sv
+        .splice(2.., ["e", "f", "g", "h"].map(String::from))
+        .unwrap();
+    assert!(removed.is_empty());
+    assert!(sv.is_full());
+
+    let res = sv.splice(1..=1, ["1", "2"].map(String::from));
+    assert!(res.is_err());
+    assert_eq!(sv.as_slice(), &["c", "d", "e", "f", "g", "h"]);
+}
+
+#[test]
+fn try_drain_invalid_ranges() {
+    let mut sv = StackVec::<i32, 10>::from_array([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = sv.try_drain(5..2).is_none();
+    assert!(reversed);
+    assert!(sv.try_drain(..=usize::MAX).is_none());
+    assert!(sv.try_drain(3..11).is_none());
+    assert!(sv.try_drain(10..).unwrap().next().is_none());
+    assert_eq!(sv.len(), 10);
+}
+
+#[test]
+#[should_panic(expected = "Invalid range for a StackVec of length 3")]
+fn drain_out_of_bounds_must_panic() {
+    let mut sv = StackVec::from_array([1, 2, 3]);
+    sv.drain(1..4);
+}
+
+#[test]
+fn leaked_drain() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut sv = StackVec::<_, 5>::generate(|| rc.clone());
+
+    let mut d = sv.drain(1..3);
+    d.next();
+    std::mem::forget(d);
+
+    /* Only the prefix before the drained range remains */
+    assert_eq!(sv.len(), 1);
+    drop(sv);
+    assert_eq!(Rc::strong_count(&rc), 4);
+}
+
+#[test]
+fn drain_skipping() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut sv = StackVec::<_, 10>::new();
+    sv.extend_from_iter((0..10).map(|i| (i, rc.clone())));
+
+    let mut d = sv.drain(1..9);
+    assert_eq!(d.nth(2).map(|(i, _)| i), Some(3));
+    assert_eq!(d.nth_back(1).map(|(i, _)| i), Some(7));
+    assert_eq!(d.as_slice().len(), 3);
+    assert_eq!(d.nth(5), None);
+    drop(d);
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    assert_eq!(sv.drain(..).last().map(|(i, _)| i), Some(9));
+    assert!(sv.is_empty());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn drain_drop_count() {
+    use std::cell::Cell;
+
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let mut sv = StackVec::<_, 8>::new();
+    sv.extend_from_iter((0..8).map(|_| Counted(&drops)));
+
+    let last = sv.drain(2..6).last();
+    assert_eq!(drops.get(), 3);
+    drop(last);
+    assert_eq!(drops.get(), 4);
+    assert_eq!(sv.len(), 4);
+
+    let mut d = sv.drain(1..);
+    d.next();
+    assert_eq!(drops.get(), 5);
+    drop(d);
+    assert_eq!(drops.get(), 7);
+    assert_eq!(sv.len(), 1);
+}
+
+#[test]
+fn into_iter() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut sv = StackVec::<_, 8>::new();
+    sv.extend_from_iter((0..8).map(|i| (i, rc.clone())));
+
+    let mut it = sv.into_iter();
+    assert_eq!(it.len(), 8);
+    assert_eq!(it.next().map(|(i, _)| i), Some(0));
+    assert_eq!(it.next_back().map(|(i, _)| i), Some(7));
+    assert_eq!(it.nth(1).map(|(i, _)| i), Some(2));
+    it.as_mut_slice()[0].0 = 30;
+
+    let cloned = it.clone();
+    assert!(cloned.map(|(i, _)| i).eq([30, 4, 5, 6]));
+
+    assert_eq!(it.nth_back(2).map(|(i, _)| i), Some(4));
+    assert_eq!(it.as_slice().len(), 1);
+    assert_eq!(Rc::strong_count(&rc), 2);
+    drop(it);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let mut sv = StackVec::from_array([1, 2, 3]);
+    for x in &mut sv {
+        *x *= 2;
+    }
+    assert!((&sv).into_iter().eq(&[2, 4, 6]));
+}
+
+#[test]
+fn cursor() {
+    let mut sv = StackVec::<String, 6>::new();
+    sv.extend_from_iter(["a", "b", "c"].map(String::from));
+
+    let mut c = sv.cursor_mut();
+    assert_eq!(c.current().map(|s| s.as_str()), Some("a"));
+    assert_eq!(c.peek_next().map(|s| s.as_str()), Some("b"));
+    assert_eq!(c.peek_prev(), None);
+
+    c.insert_after("a2".into()).unwrap();
+    c.move_next();
+    assert_eq!(c.current().map(|s| s.as_str()), Some("a2"));
+    /* Both peeks can be held at once */
+    let (prev, next) = (c.peek_prev(), c.peek_next());
+    assert_eq!((prev.unwrap().as_str(), next.unwrap().as_str()), ("a", "b"));
+    c.move_next();
+    assert_eq!(c.remove_current().as_deref(), Some("b"));
+    c.insert_before("x".into()).unwrap();
+    assert_eq!(c.index(), 3);
+    assert!(c.move_next());
+    assert!(!c.move_next());
+    c.insert_after("end".into()).unwrap();
+    c.insert_before("y".into()).unwrap();
+    assert_eq!(c.insert_before("full".into()), Err("full".into()));
+    drop(c);
+
+    assert_eq!(sv.as_slice(), &["a", "a2", "x", "c", "y", "end"]);
+}
+
+#[test]
+fn collect_stackvec() {
+    use stack_vector::{CapacityError, IteratorExt};
+
+    let sv = "a,b,c".split(',').collect_stackvec::<3>();
+    assert_eq!(sv.as_slice(), &["a", "b", "c"]);
+
+    let sv = (0..3).try_collect_stackvec::<5>().unwrap();
+    assert_eq!(sv.as_slice(), &[0, 1, 2]);
+
+    assert_eq!(
+        (0..6).try_collect_stackvec::<5>().err(),
+        Some(CapacityError)
+    );
+}
+
+#[test]
+fn stack_chunks() {
+    use stack_vector::IteratorExt;
+
+    let chunks = (0..7).stack_chunks::<3>();
+    assert_eq!(chunks.size_hint(), (3, Some(3)));
+
+    let sums = chunks.map(|c| c.iter().sum::<i32>()).collect::<Vec<_>>();
+    assert_eq!(sums, [3, 12, 6]);
+
+    assert!(
+        std::iter::empty::<u8>()
+            .stack_chunks::<4>()
+            .next()
+            .is_none()
+    );
+}
+
+#[test]
+fn cow() {
+    let data = [String::from("a"), String::from("b")];
+
+    let mut cow = StackCow::<String, 3>::from(&data[..]);
+    assert!(cow.is_borrowed());
+    assert_eq!(cow.len(), 2);
+
+    cow.to_mut().unwrap().push("c".into());
+    assert!(cow.is_owned());
+    assert_eq!(&*cow, &["a", "b", "c"]);
+    assert_eq!(data.len(), 2);
+
+    let owned = cow.into_owned().unwrap();
+    assert_eq!(owned.as_slice(), &["a", "b", "c"]);
+
+    let mut too_big = StackCow::<String, 1>::from(&data[..]);
+    assert!(too_big.to_mut().is_err());
+    assert!(too_big.is_borrowed());
+    assert!(too_big.into_owned().is_err());
+
+    assert!(
+        StackCow::<String, 3>::from(owned)
+            == StackCow::from(&["a", "b", "c"].map(String::from)[..])
+    );
+}
+
+#[test]
+fn type_level_builder() {
+    let arr: [String; 3] = StackVecBuilder::new()
+        .push("a".into())
+        .push("b".into())
+        .push("c".into())
+        .finish();
+    assert_eq!(arr, ["a", "b", "c"]);
+
+    let partial = StackVecBuilder::<String, 4, 0>::new()
+        .push("x".into())
+        .push("y".into());
+    assert_eq!(partial.len(), 2);
+    assert_eq!(partial.as_slice(), &["x", "y"]);
+
+    let mut sv = partial.into_stack_vec();
+    sv.push("z".into());
+    assert_eq!(sv.as_slice(), &["x", "y", "z"]);
+
+    /* Dropping a partial builder drops the pushed elements */
+    let rc = std::rc::Rc::new(());
+    let builder = StackVecBuilder::<_, 3, 0>::new()
+        .push(rc.clone())
+        .push(rc.clone());
+    assert_eq!(std::rc::Rc::strong_count(&rc), 3);
+    drop(builder);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn non_empty() {
+    let mut ne = NonEmptyStackVec::<String, 3>::new("a".into());
+    assert_eq!(ne.first(), ne.last());
+
+    ne.push("b".into());
+    ne.last_mut().push('!');
+    assert_eq!(ne.try_push("c".into()), Ok(()));
+    assert!(ne.try_push("d".into()).is_err());
+    assert_eq!(ne.as_slice(), &["a", "b!", "c"]);
+
+    ne.truncate(0);
+    assert_eq!(ne.as_slice(), &["a"]);
+    assert_eq!(ne.pop(), None);
+
+    let sv: StackVec<String, 3> = ne.into();
+    let ne = NonEmptyStackVec::try_from(sv).ok().unwrap();
+    assert_eq!(ne.first(), "a");
+
+    let empty = StackVec::<String, 3>::new();
+    assert!(NonEmptyStackVec::try_from(empty).is_err());
+    assert!(NonEmptyStackVec::from_stack_vec(StackVec::<i32, 2>::new()).is_none());
+}
+
+#[test]
+fn pop_while() {
+    let mut sv = StackVec::<String, 6>::new();
+    sv.extend_from_iter(["a", "b", "", "c", "", ""].map(String::from));
+
+    assert_eq!(sv.pop_while(|s| s.is_empty()).count(), 2);
+    assert_eq!(sv.len(), 4);
+
+    assert_eq!(sv.pop_while(|s| s != "a").next().as_deref(), Some("c"));
+    assert_eq!(sv.as_slice(), &["a", "b", ""]);
+
+    let popped = sv.pop_while(|s| s != "a").collect::<Vec<_>>();
+    assert_eq!(popped, ["", "b"]);
+
+    {
+        let mut it = sv.pop_while(|_| false);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+    assert_eq!(sv.pop_while(|_| true).count(), 1);
+    assert!(sv.is_empty());
+}
+
+#[test]
+fn clone_clones_each_element() {
+    let rc = std::rc::Rc::new(());
+    let mut sv = StackVec::<_, 4>::new();
+    sv.push(rc.clone());
+    sv.push(rc.clone());
+
+    let cloned = sv.clone();
+    assert_eq!(std::rc::Rc::strong_count(&rc), 5);
+    drop(sv);
+    drop(cloned);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+
+    let strings = StackVec::<String, 3>::from_array(["a", "b", "c"].map(String::from));
+    let mut it = strings.clone().into_iter();
+    it.next();
+    let rest = it.clone().collect::<Vec<_>>();
+    assert_eq!(rest, ["b", "c"]);
+    assert_eq!(strings.as_slice(), &["a", "b", "c"]);
+}
+
+#[test]
+fn bitwise_clone_fast_paths() {
+    let mut bytes = StackVec::<u8, 8>::new();
+    bytes.extend_from_slice(b"abc").unwrap();
+    assert!(bytes.extend_from_slice(b"defghi").is_err());
+    bytes.fill_to_capacity(b'-');
+    assert_eq!(bytes.as_slice(), b"abc-----");
+    assert_eq!(bytes.clone().as_slice(), bytes.as_slice());
+
+    for n in 0..=13 {
+        let sv = StackVec::<char, 13>::from_element_n('x', n);
+        assert_eq!(sv.len(), n);
+        assert!(sv.iter().all(|&c| c == 'x'));
+    }
+
+    let mut floats = StackVec::<f64, 5>::new();
+    floats.extend_from_slice(&[1.5, 2.5]).unwrap();
+    floats.extend_repeating(&[0.5], 3).unwrap();
+    assert_eq!(floats.as_slice(), &[1.5, 2.5, 0.5, 0.5, 0.5]);
+}
+
+#[test]
+fn custom_clone_is_called() {
+    use std::cell::Cell;
+
+    /* Copy, but with a clone that has side effects */
+    #[derive(Copy)]
+    struct Counted<'a>(&'a Cell<usize>);
+
+    #[allow(clippy::non_canonical_clone_impl)]
+    impl Clone for Counted<'_> {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            *self
+        }
+    }
+
+    let clones = Cell::new(0);
+    let mut sv = StackVec::<Counted, 6>::new();
+    sv.extend_from_slice(&[Counted(&clones); 2]).unwrap();
+    assert_eq!(clones.get(), 2);
+    let _ = sv.clone();
+    assert_eq!(clones.get(), 4);
+    sv.fill_to_capacity(Counted(&clones));
+    assert_eq!(clones.get(), 7);
+}
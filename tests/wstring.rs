@@ -0,0 +1,47 @@
+use stack_vector::StackWString;
+
+#[test]
+fn push_and_pop() {
+    let mut s = StackWString::<4>::new();
+    s.push('a').unwrap();
+    /* Takes a surrogate pair */
+    s.push('𝄞').unwrap();
+    assert_eq!(s.len(), 3);
+    assert_eq!(s.push('😀'), Err('😀'));
+    s.push('b').unwrap();
+    assert!(s.is_full());
+    assert_eq!(s, "a𝄞b");
+
+    assert_eq!(s.pop(), Some('b'));
+    assert_eq!(s.pop(), Some('𝄞'));
+    assert_eq!(s.pop(), Some('a'));
+    assert_eq!(s.pop(), None);
+}
+
+#[test]
+fn from_str_and_display() {
+    let s = StackWString::<8>::try_from("héllo").unwrap();
+    assert_eq!(s.to_string(), "héllo");
+    assert_eq!(format!("{s:?}"), "\"héllo\"");
+    assert!(s.chars().eq("héllo".chars()));
+
+    assert!(StackWString::<2>::try_from("abc").is_err());
+
+    let mut s = StackWString::<4>::new();
+    s.push_str("ab").unwrap();
+    assert!(s.push_str("cde").is_err());
+    assert_eq!(s, "ab");
+}
+
+#[test]
+fn nul_terminated() {
+    let mut s = StackWString::<3>::try_from("hi").unwrap();
+    assert_eq!(s.as_nul_terminated().unwrap(), &[104, 105, 0]);
+    assert_eq!(s.len(), 2);
+
+    s.push('!').unwrap();
+    assert!(s.as_nul_terminated().is_err());
+
+    let mut empty = StackWString::<1>::new();
+    assert_eq!(empty.as_nul_terminated().unwrap(), &[0]);
+}